@@ -5,10 +5,13 @@ use esp_idf_hal::{
     i2c::I2cDriver,
     delay::FreeRtos,
 };
+use drivers::Polarity;
 use services::wifi_config::{self, WifiConfig};
 use cores::WifiAdapter;
 
 pub type LedPin = PinDriver<'static, Gpio2, Output>;
+/// LED onboard board ESP32-C3 menyala saat pin low
+pub const LED_POLARITY: Polarity = Polarity::ActiveLow;
 pub type I2c = I2cDriver<'static>;
 pub type Delay = FreeRtos;
 pub type Wifi = WifiAdapter;
@@ -12,43 +12,35 @@ use esp_idf_svc::{
     nvs::EspDefaultNvsPartition,
 };
 
-use drivers::{Led, LcdI2c, WifiDriver};
+use drivers::{backpack, Backpack, Led, LcdGeometry, LcdI2c, WifiDriver};
 use services::{LcdDisplay, WifiService};
 use cores::{WifiAdapter};
 
 use crate::config::{self, LedPin, I2c, Delay, Wifi};
 
-pub struct Hardware {
-    pub led: Led<LedPin>,
-    pub display: LcdDisplay<I2c, Delay>,
-    pub wifi: WifiService<Wifi>,
-}
+pub type Hardware = cores::Hardware<LedPin, LcdDisplay<I2c, Delay>, Wifi>;
 
 pub fn init() -> Result<Hardware> {
     let peripherals = Peripherals::take()?;
 
     // ===== LED =====
     let gpio_led = PinDriver::output(peripherals.pins.gpio2)?;
-    let led = Led::new(gpio_led);
+    let led = Led::with_polarity(gpio_led, config::LED_POLARITY);
 
     // ===== I2C + LCD =====
-    let i2c = I2cDriver::new(
+    let mut i2c = I2cDriver::new(
         peripherals.i2c0,
         peripherals.pins.gpio8,
         peripherals.pins.gpio9,
         &I2cConfig::new().baudrate(100_u32.kHz().into()),
     )?;
 
-    let mut delay = FreeRtos;
-    let lcd = match LcdI2c::new(i2c, 0x27, &mut delay) {
-        Ok(lcd) => Some(lcd),
-        Err(_) => {
-            println!("LCD init failed, disabled");
-            None
-        }
-    };
+    // backpack PCF8574A ada di 0x3F; default 0x27 bila tidak ada yang menjawab
+    let addr = backpack::probe(&mut i2c).unwrap_or(0x27);
 
-    let display = LcdDisplay::new(lcd, delay);
+    // init di operasi pertama; LCD yang belum terpasang dicoba lagi tiap flush
+    let lcd = LcdI2c::uninit(i2c, addr, LcdGeometry::LCD_16X2, Backpack::default());
+    let display = LcdDisplay::deferred(lcd, FreeRtos);
 
     // ===== WiFi =====
     let sysloop = EspSystemEventLoop::take()?;
@@ -1,13 +1,13 @@
 mod init;
 mod config;
 mod hardware;
-mod app;
 
 use anyhow::Result;
+use esp_idf_hal::delay::FreeRtos;
 
 fn main() -> Result<()> {
     init::system()?;
     let hw = hardware::init()?;
-    let mut app = app::App::new(hw);
+    let mut app = cores::App::new(hw, FreeRtos);
     app.run();
 }
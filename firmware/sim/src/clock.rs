@@ -0,0 +1,66 @@
+use core::cell::{Cell, RefCell};
+use std::io::Write;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use embedded_hal::delay::DelayNs;
+use testkit::Pcf8574Lcd;
+
+/// Delay bus LCD: emulator tidak butuh timing
+pub struct BusDelay;
+
+impl DelayNs for BusDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Delay main loop: gambar ulang terminal lalu tidur sungguhan
+pub struct Clock {
+    pub screen: Pcf8574Lcd,
+    pub led: Rc<Cell<bool>>,
+    pub wifi: Rc<Cell<bool>>,
+    // frame terakhir; terminal hanya digambar ulang bila berubah
+    pub last: RefCell<String>,
+}
+
+impl Clock {
+    fn render(&self) {
+        let screen = self.screen.screen();
+        let border = "-".repeat(screen.cols());
+
+        let mut out = String::from("\x1b[2J\x1b[H");
+        out.push_str(&format!("+{}+\n", border));
+        for row in screen.lines() {
+            let row = if screen.display_on() { row } else { " ".repeat(screen.cols()) };
+            out.push_str(&format!("|{}|\n", row));
+        }
+        out.push_str(&format!("+{}+\n", border));
+        out.push_str(&format!(
+            "LED: {}  WiFi: {}  Backlight: {}\n",
+            if self.led.get() { "ON " } else { "OFF" },
+            if self.wifi.get() { "up" } else { "down" },
+            if screen.backlight() { "on" } else { "off" },
+        ));
+
+        if *self.last.borrow() == out {
+            return;
+        }
+
+        let mut stdout = std::io::stdout().lock();
+        let _ = stdout.write_all(out.as_bytes());
+        let _ = stdout.flush();
+        *self.last.borrow_mut() = out;
+    }
+}
+
+impl DelayNs for Clock {
+    fn delay_ns(&mut self, ns: u32) {
+        self.render();
+        thread::sleep(Duration::from_nanos(ns as u64));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.render();
+        thread::sleep(Duration::from_millis(ms as u64));
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+
+use drivers::{Led, LcdGeometry, LcdI2c};
+use services::{LcdDisplay, WifiService};
+use testkit::Pcf8574Lcd;
+
+use crate::clock::{BusDelay, Clock};
+use crate::led::SimPin;
+use crate::wifi::ScriptedWifi;
+
+pub type Hardware = cores::Hardware<SimPin, LcdDisplay<Pcf8574Lcd, BusDelay>, ScriptedWifi>;
+
+pub fn init(geometry: LcdGeometry, wifi_script: &str) -> Result<(Hardware, Clock)> {
+    // ===== LED =====
+    let pin = SimPin::default();
+    let led_level = pin.level();
+    let led = Led::new(pin);
+
+    // ===== I2C + LCD =====
+    let bus = Pcf8574Lcd::new(0x27, geometry.cols as usize, geometry.rows as usize);
+    let screen = bus.clone();
+
+    let mut delay = BusDelay;
+    let lcd = LcdI2c::new(bus, 0x27, geometry, &mut delay).ok();
+    let display = LcdDisplay::new(lcd, delay);
+
+    // ===== WiFi =====
+    let wifi = ScriptedWifi::parse(wifi_script)?;
+    let link = wifi.link();
+    let wifi = WifiService::new(wifi, "sim", "sim");
+
+    let clock = Clock {
+        screen,
+        led: led_level,
+        wifi: link,
+        last: Default::default(),
+    };
+
+    Ok((Hardware { led, display, wifi }, clock))
+}
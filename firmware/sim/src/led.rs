@@ -0,0 +1,33 @@
+use core::cell::Cell;
+use core::convert::Infallible;
+use std::rc::Rc;
+
+use embedded_hal::digital::{ErrorType, OutputPin};
+
+/// Pin LED virtual, state bisa dibaca renderer
+#[derive(Clone, Default)]
+pub struct SimPin {
+    level: Rc<Cell<bool>>,
+}
+
+impl SimPin {
+    pub fn level(&self) -> Rc<Cell<bool>> {
+        self.level.clone()
+    }
+}
+
+impl ErrorType for SimPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for SimPin {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.level.set(true);
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.level.set(false);
+        Ok(())
+    }
+}
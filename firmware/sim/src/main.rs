@@ -0,0 +1,49 @@
+//! Simulator host: menjalankan `cores::App` dengan perangkat virtual
+//!
+//! cargo run -p sim -- --wifi down,down,up --lcd 20x4
+
+mod clock;
+mod hardware;
+mod led;
+mod wifi;
+
+use anyhow::{bail, Context, Result};
+use drivers::LcdGeometry;
+
+/// "16x2", "20x4", ...
+fn parse_geometry(text: &str) -> Result<LcdGeometry> {
+    let (cols, rows) = text.split_once('x').context("expected COLSxROWS, e.g. 20x4")?;
+    let geometry = LcdGeometry {
+        cols: cols.parse()?,
+        rows: rows.parse()?,
+    };
+
+    if geometry.rows == 0 || geometry.rows > 4 || geometry.cols == 0 || geometry.cols > 40 {
+        bail!("unsupported LCD size {}", text);
+    }
+    Ok(geometry)
+}
+
+fn main() -> Result<()> {
+    let mut wifi_script = String::from("up");
+    let mut geometry = LcdGeometry::LCD_16X2;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--wifi" => match args.next() {
+                Some(script) => wifi_script = script,
+                None => bail!("--wifi needs a script, e.g. down,up"),
+            },
+            "--lcd" => match args.next() {
+                Some(size) => geometry = parse_geometry(&size)?,
+                None => bail!("--lcd needs a size, e.g. 20x4"),
+            },
+            other => bail!("unknown argument '{}'", other),
+        }
+    }
+
+    let (hw, clock) = hardware::init(geometry, &wifi_script)?;
+    let mut app = cores::App::new(hw, clock);
+    app.run();
+}
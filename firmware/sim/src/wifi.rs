@@ -0,0 +1,58 @@
+use core::cell::Cell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use anyhow::{bail, Result};
+use drivers::wifi::WifiStatus;
+use services::wifi::Wifi;
+
+/// WiFi palsu: hasil tiap `connect` diambil dari skrip
+pub struct ScriptedWifi {
+    script: VecDeque<bool>,
+    last: bool,
+    link: Rc<Cell<bool>>,
+}
+
+impl ScriptedWifi {
+    /// Skrip: daftar "up"/"down" dipisah koma, mis. "down,down,up".
+    /// Setelah skrip habis, hasil terakhir diulang.
+    pub fn parse(script: &str) -> Result<Self> {
+        let mut steps = VecDeque::new();
+        for step in script.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match step {
+                "up" => steps.push_back(true),
+                "down" => steps.push_back(false),
+                other => bail!("unknown wifi step '{}'", other),
+            }
+        }
+
+        Ok(Self {
+            script: steps,
+            last: true,
+            link: Rc::new(Cell::new(false)),
+        })
+    }
+
+    pub fn link(&self) -> Rc<Cell<bool>> {
+        self.link.clone()
+    }
+}
+
+impl Wifi for ScriptedWifi {
+    fn connect(&mut self, _ssid: &str, _password: &str) -> WifiStatus {
+        if let Some(step) = self.script.pop_front() {
+            self.last = step;
+        }
+        self.link.set(self.last);
+
+        if self.last {
+            WifiStatus::Connected
+        } else {
+            WifiStatus::Disconnected
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        self.link.get()
+    }
+}
@@ -0,0 +1,156 @@
+use embedded_hal::delay::DelayNs;
+use drivers::{Led, LedOutput, Pattern};
+use drivers::wifi::WifiStatus;
+use services::{Align, TextDisplay, WifiService};
+use services::wifi::Wifi;
+
+use crate::Controller;
+
+/// Kumpulan perangkat yang dipakai App (board asli atau simulator)
+pub struct Hardware<LED, DISP, W>
+where
+    LED: LedOutput,
+    DISP: TextDisplay,
+    W: Wifi,
+{
+    pub led: Led<LED>,
+    pub display: DISP,
+    pub wifi: WifiService<W>,
+}
+
+/// Periode main loop
+const LOOP_MS: u32 = 20;
+/// Jarak antar cek status WiFi
+const STATUS_MS: u64 = 5000;
+
+/// LED saat WiFi gagal: "W" dalam Morse
+const WIFI_FAILED: Pattern = Pattern::Morse { text: "W", unit_ms: 200 };
+
+/// Runtime application (logic only)
+pub struct App<LED, DISP, W, T>
+where
+    LED: LedOutput,
+    DISP: TextDisplay,
+    W: Wifi,
+{
+    // LED status dinyalakan/dimatikan pengguna (mis. lewat tombol)
+    ctrl: Controller,
+    hw: Hardware<LED, DISP, W>,
+    delay: T,
+    now_ms: u64,
+    next_status_ms: u64,
+    status: Option<WifiStatus>,
+    led_fault: bool,
+}
+
+impl<LED, DISP, W, T> App<LED, DISP, W, T>
+where
+    LED: LedOutput,
+    DISP: TextDisplay,
+    W: Wifi,
+    T: DelayNs,
+{
+    pub fn new(hw: Hardware<LED, DISP, W>, delay: T) -> Self {
+        Self {
+            ctrl: Controller::with_state(true),
+            hw,
+            delay,
+            now_ms: 0,
+            next_status_ms: 0,
+            status: None,
+            led_fault: false,
+        }
+    }
+
+    pub fn run(&mut self) -> ! {
+        self.boot();
+
+        loop {
+            self.step(self.now_ms);
+            self.delay.delay_ms(LOOP_MS);
+            self.now_ms += LOOP_MS as u64;
+        }
+    }
+
+    /// Boot sequence
+    pub fn boot(&mut self) {
+        let display = &mut self.hw.display;
+        display.clear();
+        display.write_at(0, 0, "ESP32-C3 Mini");
+        display.write_at(0, 1, "System Ready");
+        display.write_at(0, 1, "LED: OFF");
+        display.write_at(0, 0, "Hello ESP32");
+        display.flush();
+
+        let _ = self.hw.wifi.start();
+        display.write_line(0, "WiFi status:", Align::Left);
+        display.write_line(1, "Connecting...", Align::Left);
+        display.flush();
+    }
+
+    /// Satu putaran main loop; tidak memblok, status WiFi dicek tiap `STATUS_MS`
+    pub fn step(&mut self, now_ms: u64) {
+        if now_ms >= self.next_status_ms {
+            self.next_status_ms = now_ms + STATUS_MS;
+            self.update_status();
+        }
+
+        // gagal tulis diulang di tick berikutnya; LED yang dimatikan ditulis padam lagi
+        let result = if self.ctrl.state() || !self.led_fault {
+            self.hw.led.tick(now_ms)
+        } else {
+            self.hw.led.set(false)
+        };
+        self.led_fault = result.is_err();
+    }
+
+    /// Nyalakan/matikan LED status; mengembalikan state baru.
+    /// Saat dinyalakan lagi, pola status WiFi terakhir dilanjutkan.
+    pub fn toggle_led(&mut self) -> bool {
+        let on = self.ctrl.toggle();
+        if on {
+            if let Some(status) = self.status {
+                self.hw.led.play(status_pattern(status));
+            }
+        } else {
+            self.led_fault = self.hw.led.set(false).is_err();
+        }
+        on
+    }
+
+    /// LED status aktif (state `Controller`)
+    pub fn led_enabled(&self) -> bool {
+        self.ctrl.state()
+    }
+
+    /// Penulisan pin LED terakhir gagal
+    pub fn led_fault(&self) -> bool {
+        self.led_fault
+    }
+
+    fn update_status(&mut self) {
+        let status = self.hw.wifi.poll();
+        let text = match status {
+            WifiStatus::Connected => "Connected.",
+            WifiStatus::Disconnected => "Failed.",
+        };
+
+        self.hw.display.write_line(1, text, Align::Left);
+        self.hw.display.flush();
+
+        // pola baru hanya saat status berubah agar tidak mulai ulang
+        if self.status != Some(status) {
+            self.status = Some(status);
+            if self.ctrl.state() {
+                self.hw.led.play(status_pattern(status));
+            }
+        }
+    }
+}
+
+fn status_pattern(status: WifiStatus) -> Pattern {
+    match status {
+        WifiStatus::Connected => Pattern::Heartbeat,
+        WifiStatus::Disconnected => WIFI_FAILED,
+    }
+}
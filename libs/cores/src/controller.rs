@@ -1,3 +1,4 @@
+#[derive(Default)]
 pub struct Controller {
     state: bool,
 }
@@ -7,6 +8,10 @@ impl Controller {
         Self { state: false }
     }
 
+    pub fn with_state(state: bool) -> Self {
+        Self { state }
+    }
+
     pub fn toggle(&mut self) -> bool {
         self.state = !self.state;
         self.state
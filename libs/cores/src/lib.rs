@@ -1,7 +1,11 @@
+mod app;
 mod controller;
+#[cfg(target_os = "espidf")]
 mod wifi;
 
+pub use app::{App, Hardware};
 pub use controller::Controller;
+#[cfg(target_os = "espidf")]
 pub use wifi::WifiAdapter;
 
 #[cfg(test)]
@@ -11,7 +15,7 @@ mod tests {
     #[test]
     fn controller_initial_state_is_false() {
         let ctrl = Controller::new();
-        assert_eq!(ctrl.state(), false);
+        assert!(!ctrl.state());
     }
 
     #[test]
@@ -19,9 +23,106 @@ mod tests {
         let mut ctrl = Controller::new();
 
         let s1 = ctrl.toggle();
-        assert_eq!(s1, true);
+        assert!(s1);
 
         let s2 = ctrl.toggle();
-        assert_eq!(s2, false);
+        assert!(!s2);
+    }
+
+    // ===== APP (VIRTUAL HARDWARE) =====
+
+    use drivers::{Led, LcdGeometry, LcdI2c};
+    use services::{LcdDisplay, WifiService};
+    use testkit::{MockDelay, MockPin, MockWifi, Pcf8574Lcd};
+
+    type TestApp = App<MockPin, LcdDisplay<Pcf8574Lcd, MockDelay>, MockWifi, MockDelay>;
+
+    fn app(wifi: &[bool]) -> (MockPin, Pcf8574Lcd, TestApp) {
+        let pin = MockPin::new(false);
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay).ok();
+        let hw = Hardware {
+            led: Led::new(pin.clone()),
+            display: LcdDisplay::new(lcd, delay),
+            wifi: WifiService::new(MockWifi::script(wifi), "ssid", "pass"),
+        };
+
+        (pin, bus, App::new(hw, MockDelay::new()))
+    }
+
+    #[test]
+    fn app_step_shows_wifi_status() {
+        let (pin, bus, mut app) = app(&[false, true]);
+
+        app.boot();
+        assert_eq!(bus.lines(), ["WiFi status:    ", "Connecting...   "]);
+
+        app.step(0);
+        assert_eq!(bus.lines(), ["WiFi status:    ", "Connected.      "]);
+        // heartbeat dimulai tanpa memblok
+        assert_eq!(pin.history(), [true]);
+
+        app.step(150);
+        assert_eq!(pin.history(), [true, false]);
+    }
+
+    #[test]
+    fn app_signals_wifi_failure_in_morse() {
+        let (pin, bus, mut app) = app(&[false, false, false]);
+        app.boot();
+
+        // "W" = .-- : titik 200 ms, jeda, garis 600 ms
+        let levels: Vec<bool> = [0, 100, 200, 400, 900, 1000, 1200]
+            .into_iter()
+            .map(|t| {
+                app.step(t);
+                pin.level()
+            })
+            .collect();
+        assert_eq!(bus.lines()[1], "Failed.         ");
+        assert_eq!(levels, [true, true, false, true, true, false, true]);
+
+        // status dicek lagi setelah 5 detik; pola tidak dimulai ulang (bila ya, LED menyala)
+        app.step(5000);
+        assert!(!pin.level());
+    }
+
+    #[test]
+    fn app_reports_led_fault_until_write_succeeds() {
+        let (pin, _bus, mut app) = app(&[true]);
+        app.boot();
+
+        pin.fail_next(1);
+        app.step(0);
+        assert!(app.led_fault());
+
+        app.step(20);
+        assert!(!app.led_fault());
+        assert!(pin.level());
+    }
+
+    #[test]
+    fn app_toggle_led_follows_controller_state() {
+        let (pin, _bus, mut app) = app(&[false, true, false]);
+        app.boot();
+        assert!(app.led_enabled());
+
+        app.step(0);
+        assert!(pin.level());
+
+        // dimatikan: perubahan status WiFi tidak menyalakan LED
+        assert!(!app.toggle_led());
+        assert!(!pin.level());
+        for t in (20..=5000).step_by(20) {
+            app.step(t);
+        }
+        assert!(!pin.level() && !app.led_enabled());
+
+        // dinyalakan lagi: pola status terakhir (Morse gagal) dilanjutkan
+        assert!(app.toggle_led());
+        app.step(5020);
+        assert!(pin.level());
     }
 }
@@ -0,0 +1,155 @@
+//! Wiring I/O expander antara bus I2C dan pin HD44780
+
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+/// Jenis chip expander di backpack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expander {
+    /// PCF8574 / PCF8574A: satu byte tulis = nilai port
+    Pcf8574,
+    /// MCP23008: port lewat register GPIO, IODIR harus di-set output dulu
+    Mcp23008,
+}
+
+const MCP_IODIR: u8 = 0x00;
+const MCP_GPIO: u8 = 0x09;
+
+/// Nomor bit port untuk tiap pin LCD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backpack {
+    pub expander: Expander,
+    pub rs: u8,
+    /// `None` bila RW di-ground (hanya bisa tulis)
+    pub rw: Option<u8>,
+    pub en: u8,
+    pub backlight: u8,
+    /// Bit untuk D4, D5, D6, D7
+    pub data: [u8; 4],
+    /// Backlight menyala saat bit-nya low
+    pub backlight_active_low: bool,
+}
+
+impl Backpack {
+    /// Backpack biru/hitam yang umum: RS=P0 RW=P1 EN=P2 BL=P3 D4..D7=P4..P7
+    pub const PCF8574: Self = Self {
+        expander: Expander::Pcf8574,
+        rs: 0,
+        rw: Some(1),
+        en: 2,
+        backlight: 3,
+        data: [4, 5, 6, 7],
+        backlight_active_low: false,
+    };
+
+    /// Wiring sama dengan `PCF8574`, backlight lewat transistor PNP
+    pub const PCF8574_BL_INVERTED: Self = Self {
+        backlight_active_low: true,
+        ..Self::PCF8574
+    };
+
+    /// Clone mjkdz: D4..D7=P0..P3 EN=P4 RW=P5 RS=P6 BL=P7 (active-low)
+    pub const MJKDZ: Self = Self {
+        expander: Expander::Pcf8574,
+        rs: 6,
+        rw: Some(5),
+        en: 4,
+        backlight: 7,
+        data: [0, 1, 2, 3],
+        backlight_active_low: true,
+    };
+
+    /// Adafruit I2C/SPI backpack: RS=GP1 EN=GP2 D4..D7=GP3..GP6 BL=GP7
+    pub const ADAFRUIT_MCP23008: Self = Self {
+        expander: Expander::Mcp23008,
+        rs: 1,
+        rw: None,
+        en: 2,
+        backlight: 7,
+        data: [3, 4, 5, 6],
+        backlight_active_low: false,
+    };
+
+    /// Nilai port dengan semua pin kontrol low, hanya backlight
+    pub(crate) fn idle(&self, backlight: bool) -> u8 {
+        if backlight != self.backlight_active_low {
+            1 << self.backlight
+        } else {
+            0
+        }
+    }
+
+    /// Byte port untuk satu nibble: (EN high, EN low)
+    pub(crate) fn nibble_frames(&self, nibble: u8, rs: bool, backlight: bool) -> [u8; 2] {
+        let mut port = self.idle(backlight);
+
+        for (i, bit) in self.data.iter().enumerate() {
+            if nibble & (1 << i) != 0 {
+                port |= 1 << bit;
+            }
+        }
+        if rs {
+            port |= 1 << self.rs;
+        }
+
+        [port | (1 << self.en), port]
+    }
+
+    /// Frame baca busy flag: (EN high, EN low) dengan RW=1 dan D4..D7 dilepas high.
+    /// `None` bila RW tidak tersambung atau expander tidak didukung (MCP23008).
+    pub(crate) fn read_frames(&self, backlight: bool) -> Option<[u8; 2]> {
+        let rw = self.rw?;
+        if self.expander != Expander::Pcf8574 {
+            return None;
+        }
+
+        let mut port = self.idle(backlight) | (1 << rw);
+        for bit in self.data {
+            port |= 1 << bit;
+        }
+        Some([port | (1 << self.en), port])
+    }
+
+    /// Busy flag (D7) dari nilai port yang dibaca saat EN high
+    pub(crate) fn busy(&self, port: u8) -> bool {
+        port & (1 << self.data[3]) != 0
+    }
+
+    /// Transaksi konfigurasi sebelum port dipakai (arah pin MCP23008)
+    pub(crate) fn setup(&self) -> Option<[u8; 2]> {
+        match self.expander {
+            Expander::Pcf8574 => None,
+            Expander::Mcp23008 => Some([MCP_IODIR, 0x00]),
+        }
+    }
+
+    /// Byte I2C untuk menulis `port`
+    pub(crate) fn port_write<'a>(&self, port: u8, buf: &'a mut [u8; 2]) -> &'a [u8] {
+        match self.expander {
+            Expander::Pcf8574 => {
+                buf[0] = port;
+                &buf[..1]
+            }
+            Expander::Mcp23008 => {
+                *buf = [MCP_GPIO, port];
+                &buf[..]
+            }
+        }
+    }
+}
+
+impl Default for Backpack {
+    fn default() -> Self {
+        Self::PCF8574
+    }
+}
+
+/// Rentang alamat PCF8574/MCP23008 (0x20..0x27) dan PCF8574A (0x38..0x3F)
+pub const PROBE_ADDRESSES: [core::ops::RangeInclusive<u8>; 2] = [0x20..=0x27, 0x38..=0x3F];
+
+/// Cari backpack pertama yang menjawab. Hanya membaca port, jadi tidak mengubah output.
+pub fn probe<I2C: I2c<SevenBitAddress>>(i2c: &mut I2C) -> Option<u8> {
+    PROBE_ADDRESSES
+        .iter()
+        .flat_map(|range| range.clone())
+        .find(|&addr| i2c.read(addr, &mut [0]).is_ok())
+}
@@ -1,157 +1,375 @@
-use core::ffi::c_void;
-use core::ptr;
-use core::sync::atomic::{AtomicBool, Ordering};
+//! BLE GATT peripheral (NimBLE) dengan satu karakteristik write.
+//!
+//! Driver ESP hanya ada dengan feature `ble` di target espidf dan butuh
+//! NimBLE aktif di sdkconfig (lihat `firmware/app/sdkconfig.defaults`).
+//! Pelacakan koneksi murni Rust sehingga bisa dites di host.
 
-use esp_idf_sys::*;
+/// Batas koneksi bersamaan (default `CONFIG_BT_NIMBLE_MAX_CONNECTIONS`)
+pub const MAX_CONNECTIONS: usize = 3;
 
-static BLE_STARTED: AtomicBool = AtomicBool::new(false);
-static BLE_CONNECTED: AtomicBool = AtomicBool::new(false);
+/// Handle koneksi yang sedang aktif
+#[derive(Debug, Default)]
+pub struct Connections {
+    handles: heapless::Vec<u16, MAX_CONNECTIONS>,
+}
+
+impl Connections {
+    pub const fn new() -> Self {
+        Self {
+            handles: heapless::Vec::new(),
+        }
+    }
+
+    /// Catat koneksi baru; false bila sudah tercatat atau tabel penuh
+    pub fn connect(&mut self, handle: u16) -> bool {
+        !self.handles.contains(&handle) && self.handles.push(handle).is_ok()
+    }
 
-/// Callback user (raw bytes)
-static mut WRITE_CB: Option<fn(&[u8])> = None;
+    /// Hapus koneksi; false bila handle tidak dikenal
+    pub fn disconnect(&mut self, handle: u16) -> bool {
+        match self.handles.iter().position(|&h| h == handle) {
+            Some(i) => {
+                self.handles.swap_remove(i);
+                true
+            }
+            None => false,
+        }
+    }
 
-pub struct BleDriver;
+    pub fn handles(&self) -> &[u16] {
+        &self.handles
+    }
 
-impl BleDriver {
-    pub fn new() -> Result<Self, EspError> {
-        Ok(Self)
+    pub fn len(&self) -> usize {
+        self.handles.len()
     }
 
-    pub fn on_write(&mut self, cb: fn(&[u8])) {
-        unsafe { WRITE_CB = Some(cb) }
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.handles.clear();
+    }
+}
+
+#[cfg(all(target_os = "espidf", feature = "ble"))]
+pub use esp::BleDriver;
+
+#[cfg(all(target_os = "espidf", feature = "ble"))]
+mod esp {
+    extern crate alloc;
+
+    use alloc::boxed::Box;
+    use core::cell::RefCell;
+    use core::ffi::{c_void, CStr};
+    use core::ptr;
+    use core::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+    use critical_section::Mutex;
+    use esp_idf_sys::*;
+
+    use super::{Connections, MAX_CONNECTIONS};
+
+    type WriteCallback = Box<dyn FnMut(&[u8]) + Send>;
+
+    const DEVICE_NAME: &CStr = c"ESP32-BLE";
+
+    static STARTED: AtomicBool = AtomicBool::new(false);
+    /// Diset di awal `stop`; callback GAP berhenti advertising dan menolak koneksi baru
+    static STOPPING: AtomicBool = AtomicBool::new(false);
+    /// Kode error terakhir dari callback NimBLE (0 = tidak ada)
+    static LAST_ERROR: AtomicI32 = AtomicI32::new(0);
+    static WRITE_CB: Mutex<RefCell<Option<WriteCallback>>> = Mutex::new(RefCell::new(None));
+    static CONNECTIONS: Mutex<RefCell<Connections>> = Mutex::new(RefCell::new(Connections::new()));
+
+    /// Kode kembali NimBLE/ESP-IDF: 0 = sukses, selain itu diteruskan apa adanya
+    fn check(rc: i32) -> Result<(), EspError> {
+        match EspError::from(rc) {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
 
-    pub fn start(&mut self) -> Result<(), EspError> {
-        if BLE_STARTED.swap(true, Ordering::SeqCst) {
-            return Ok(()); // already started
+    pub struct BleDriver;
+
+    impl BleDriver {
+        pub fn new() -> Result<Self, EspError> {
+            Ok(Self)
         }
 
-        unsafe {
-            esp_nimble_hci_and_controller_init();
-            nimble_port_init();
+        /// Callback untuk data yang ditulis client; dipanggil dari task host
+        /// NimBLE di luar critical section. Mengganti callback sebelumnya.
+        pub fn on_write<F>(&mut self, cb: F)
+        where
+            F: FnMut(&[u8]) + Send + 'static,
+        {
+            critical_section::with(|cs| {
+                WRITE_CB.borrow_ref_mut(cs).replace(Box::new(cb));
+            });
+        }
+
+        /// Inisialisasi NimBLE, daftarkan GATT lalu jalankan task host.
+        /// Advertising dimulai setelah host sinkron dengan controller.
+        pub fn start(&mut self) -> Result<(), EspError> {
+            if STARTED
+                .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                .is_err()
+            {
+                return Ok(());
+            }
+
+            let result = unsafe { Self::init() };
+            if result.is_err() {
+                STARTED.store(false, Ordering::SeqCst);
+            }
+            result
+        }
+
+        pub fn is_connected(&self) -> bool {
+            critical_section::with(|cs| !CONNECTIONS.borrow_ref(cs).is_empty())
+        }
+
+        /// Salinan handle koneksi yang aktif
+        pub fn connections(&self) -> heapless::Vec<u16, MAX_CONNECTIONS> {
+            critical_section::with(|cs| {
+                heapless::Vec::from_slice(CONNECTIONS.borrow_ref(cs).handles()).unwrap_or_default()
+            })
+        }
+
+        /// Error terakhir dari callback NimBLE (advertising, dsb.)
+        pub fn last_error(&self) -> Option<EspError> {
+            EspError::from(LAST_ERROR.load(Ordering::SeqCst))
+        }
+
+        /// Putus semua koneksi lalu hentikan task host. Aman saat task host
+        /// berjalan: `nimble_port_stop` menunggu task keluar dari event loop.
+        /// Bila gagal, driver tetap dalam mode berhenti dan `stop` boleh
+        /// diulang. Jangan dipanggil dari dalam callback `on_write`.
+        pub fn stop(&mut self) -> Result<(), EspError> {
+            if !STARTED.load(Ordering::SeqCst) {
+                return Ok(());
+            }
+            // sebelum memutus koneksi agar event disconnect tidak advertising lagi
+            STOPPING.store(true, Ordering::SeqCst);
+
+            unsafe {
+                // advertising mungkin sudah berhenti; BLE_HS_EALREADY bukan error
+                let rc = ble_gap_adv_stop();
+                if rc != BLE_HS_EALREADY as i32 {
+                    check(rc)?;
+                }
+
+                for handle in self.connections() {
+                    terminate(handle)?;
+                }
+
+                check(nimble_port_stop())?;
+                check(nimble_port_deinit() as i32)?;
+            }
+
+            critical_section::with(|cs| CONNECTIONS.borrow_ref_mut(cs).clear());
+            STARTED.store(false, Ordering::SeqCst);
+            STOPPING.store(false, Ordering::SeqCst);
+            Ok(())
+        }
+
+        unsafe fn init() -> Result<(), EspError> {
+            check(nimble_port_init() as i32)?;
+
+            ble_hs_cfg.sync_cb = Some(on_sync);
+            ble_hs_cfg.reset_cb = Some(on_reset);
 
             ble_svc_gap_init();
             ble_svc_gatt_init();
 
-            ble_svc_gap_device_name_set(b"ESP32-BLE\0".as_ptr() as _);
+            let registered = check(ble_svc_gap_device_name_set(DEVICE_NAME.as_ptr()))
+                .and_then(|()| check(ble_gatts_count_cfg(GATT_DEFS.0.as_ptr())))
+                .and_then(|()| check(ble_gatts_add_svcs(GATT_DEFS.0.as_ptr())));
+            if let Err(e) = registered {
+                let _ = nimble_port_deinit();
+                return Err(e);
+            }
 
-            ble_gatts_count_cfg(GATT_DEFS.as_ptr());
-            ble_gatts_add_svcs(GATT_DEFS.as_ptr());
+            nimble_port_freertos_init(Some(host_task));
+            Ok(())
+        }
+    }
 
-            ble_gap_adv_set_fields(&ADV_FIELDS);
-            ble_gap_adv_start(
-                BLE_OWN_ADDR_PUBLIC,
-                ptr::null(),
-                BLE_HS_FOREVER as _,
-                &ADV_PARAMS,
-                Some(gap_event),
-                ptr::null_mut(),
-            );
+    /* ===================== NIMBLE CALLBACKS ===================== */
 
-            nimble_port_freertos_init(Some(ble_host_task));
+    extern "C" fn host_task(_: *mut c_void) {
+        unsafe {
+            // kembali setelah nimble_port_stop
+            nimble_port_run();
+            nimble_port_freertos_deinit();
         }
+    }
 
-        Ok(())
+    extern "C" fn on_sync() {
+        readvertise();
     }
 
-    pub fn is_connected(&self) -> bool {
-        BLE_CONNECTED.load(Ordering::SeqCst)
+    extern "C" fn on_reset(reason: i32) {
+        LAST_ERROR.store(reason, Ordering::SeqCst);
     }
 
-    pub fn stop(&mut self) -> Result<(), EspError> {
-        unsafe {
-            ble_gap_adv_stop();
-            nimble_port_stop();
-            nimble_port_deinit();
+    fn record(result: Result<(), EspError>) {
+        if let Err(e) = result {
+            LAST_ERROR.store(e.code(), Ordering::SeqCst);
         }
-        BLE_STARTED.store(false, Ordering::SeqCst);
-        Ok(())
     }
-}
 
-/* ===================== NIMBLE CALLBACKS ===================== */
+    /// Advertising lagi selama driver berjalan dan slot koneksi masih ada
+    fn readvertise() {
+        let running = STARTED.load(Ordering::SeqCst) && !STOPPING.load(Ordering::SeqCst);
+        let free = critical_section::with(|cs| CONNECTIONS.borrow_ref(cs).len() < MAX_CONNECTIONS);
+        if running && free {
+            record(unsafe { advertise() });
+        }
+    }
+
+    /// Putus satu koneksi; koneksi yang sudah hilang bukan error
+    unsafe fn terminate(handle: u16) -> Result<(), EspError> {
+        let rc = ble_gap_terminate(handle, BLE_ERR_REM_USER_CONN_TERM as u8);
+        if rc == BLE_HS_ENOTCONN as i32 {
+            return Ok(());
+        }
+        check(rc)
+    }
+
+    /// Advertising tak terbatas; field disalin NimBLE sehingga boleh lokal
+    unsafe fn advertise() -> Result<(), EspError> {
+        let mut own_addr_type = 0u8;
+        check(ble_hs_id_infer_auto(0, &mut own_addr_type))?;
+
+        let mut fields: ble_hs_adv_fields = core::mem::zeroed();
+        fields.flags = (BLE_HS_ADV_F_DISC_GEN | BLE_HS_ADV_F_BREDR_UNSUP) as u8;
+        fields.name = DEVICE_NAME.as_ptr() as *const u8;
+        fields.name_len = DEVICE_NAME.to_bytes().len() as u8;
+        fields.set_name_is_complete(1);
+        check(ble_gap_adv_set_fields(&fields))?;
 
-extern "C" fn ble_host_task(_: *mut c_void) {
-    unsafe {
-        nimble_port_run();
-        nimble_port_freertos_deinit();
+        let mut params: ble_gap_adv_params = core::mem::zeroed();
+        params.conn_mode = BLE_GAP_CONN_MODE_UND as u8;
+        params.disc_mode = BLE_GAP_DISC_MODE_GEN as u8;
+        check(ble_gap_adv_start(
+            own_addr_type,
+            ptr::null(),
+            BLE_HS_FOREVER as i32,
+            &params,
+            Some(gap_event),
+            ptr::null_mut(),
+        ))
     }
-}
 
-extern "C" fn gap_event(
-    event: *mut ble_gap_event,
-    _: *mut c_void,
-) -> i32 {
-    unsafe {
-        match (*event).type_ {
-            BLE_GAP_EVENT_CONNECT => {
-                BLE_CONNECTED.store(true, Ordering::SeqCst);
-            }
-            BLE_GAP_EVENT_DISCONNECT => {
-                BLE_CONNECTED.store(false, Ordering::SeqCst);
+    extern "C" fn gap_event(event: *mut ble_gap_event, _: *mut c_void) -> i32 {
+        unsafe {
+            let event = &*event;
+            match event.type_ as u32 {
+                BLE_GAP_EVENT_CONNECT => {
+                    let connect = event.__bindgen_anon_1.connect;
+                    if connect.status == 0 {
+                        if STOPPING.load(Ordering::SeqCst) {
+                            // tersambung saat stop berjalan; langsung diputus
+                            record(terminate(connect.conn_handle));
+                        } else {
+                            critical_section::with(|cs| {
+                                CONNECTIONS.borrow_ref_mut(cs).connect(connect.conn_handle)
+                            });
+                        }
+                    }
+                    // tetap bisa ditemukan selama slot koneksi masih ada
+                    readvertise();
+                }
+                BLE_GAP_EVENT_DISCONNECT => {
+                    let handle = event.__bindgen_anon_1.disconnect.conn.conn_handle;
+                    critical_section::with(|cs| CONNECTIONS.borrow_ref_mut(cs).disconnect(handle));
+                    readvertise();
+                }
+                BLE_GAP_EVENT_ADV_COMPLETE => readvertise(),
+                _ => {}
             }
-            _ => {}
         }
+        0
     }
-    0
-}
 
-extern "C" fn gatt_access(
-    _: u16,
-    _: u16,
-    ctxt: *mut ble_gatt_access_ctxt,
-    _: *mut c_void,
-) -> i32 {
-    unsafe {
-        let om = (*ctxt).om;
-        let len = (*om).om_len as usize;
-        let data = core::slice::from_raw_parts((*om).om_data, len);
+    extern "C" fn gatt_access(
+        _: u16,
+        _: u16,
+        ctxt: *mut ble_gatt_access_ctxt,
+        _: *mut c_void,
+    ) -> i32 {
+        let mut buf = [0u8; 512];
+        let len = unsafe {
+            let om = (*ctxt).om;
+            let mut len = 0u16;
+            if ble_hs_mbuf_to_flat(
+                om,
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len() as u16,
+                &mut len,
+            ) != 0
+            {
+                return BLE_ATT_ERR_INVALID_ATTR_VALUE_LEN as i32;
+            }
+            len as usize
+        };
 
-        if let Some(cb) = WRITE_CB {
-            cb(data);
+        // panggil di luar critical section; on_write boleh mengganti callback sementara itu
+        let cb = critical_section::with(|cs| WRITE_CB.borrow_ref_mut(cs).take());
+        if let Some(mut cb) = cb {
+            cb(&buf[..len]);
+            critical_section::with(|cs| {
+                let mut slot = WRITE_CB.borrow_ref_mut(cs);
+                if slot.is_none() {
+                    *slot = Some(cb);
+                }
+            });
         }
+        0
     }
-    0
-}
 
-/* ===================== GATT DEFINITIONS ===================== */
+    /* ===================== GATT DEFINITIONS ===================== */
+
+    /// Tabel C berisi pointer mentah; hanya dibaca NimBLE
+    #[repr(transparent)]
+    struct Table<T>(T);
+
+    unsafe impl<T> Sync for Table<T> {}
 
-static GATT_CHR: ble_gatt_chr_def = ble_gatt_chr_def {
-    uuid: &ble_uuid128_t {
-        u: ble_uuid128 {
-            u128: *b"\x01\xef\xcd\xab\x89\x67\x45\x23\x01\x23\x45\x67\x89\xab\xcd\xef",
+    static SVC_UUID: ble_uuid128_t = ble_uuid128_t {
+        u: ble_uuid_t {
+            type_: BLE_UUID_TYPE_128 as u8,
         },
-    } as *const _ as *const ble_uuid_t,
-    access_cb: Some(gatt_access),
-    flags: BLE_GATT_CHR_F_WRITE as u16,
-    ..unsafe { core::mem::zeroed() }
-};
-
-static GATT_CHRS: [ble_gatt_chr_def; 2] = [
-    GATT_CHR,
-    ble_gatt_chr_def {
-        uuid: core::ptr::null(),
-        access_cb: None,
-        flags: 0,
-        ..unsafe { core::mem::zeroed() }
-    },
-];
-
-static GATT_SVC: ble_gatt_svc_def = ble_gatt_svc_def {
-    type_: BLE_GATT_SVC_TYPE_PRIMARY as u8,
-    uuid: &ble_uuid128_t {
-        u: ble_uuid128 {
-            u128: *b"\xf0\xde\xbc\x9a\x78\x56\x34\x12\x34\x12\x56\x78\x9a\xbc\xde\xf0",
+        value: *b"\xf0\xde\xbc\x9a\x78\x56\x34\x12\x34\x12\x56\x78\x9a\xbc\xde\xf0",
+    };
+
+    static CHR_UUID: ble_uuid128_t = ble_uuid128_t {
+        u: ble_uuid_t {
+            type_: BLE_UUID_TYPE_128 as u8,
         },
-    } as *const _ as *const ble_uuid_t,
-    characteristics: GATT_CHRS.as_ptr(),
-};
-
-static GATT_DEFS: [ble_gatt_svc_def; 2] = [
-    GATT_SVC,
-    ble_gatt_svc_def {
-        type_: 0,
-        uuid: core::ptr::null(),
-        characteristics: core::ptr::null(),
-    },
-];
+        value: *b"\x01\xef\xcd\xab\x89\x67\x45\x23\x01\x23\x45\x67\x89\xab\xcd\xef",
+    };
+
+    static GATT_CHRS: Table<[ble_gatt_chr_def; 2]> = Table([
+        ble_gatt_chr_def {
+            uuid: &CHR_UUID.u,
+            access_cb: Some(gatt_access),
+            flags: (BLE_GATT_CHR_F_WRITE | BLE_GATT_CHR_F_WRITE_NO_RSP) as u16,
+            ..unsafe { core::mem::zeroed() }
+        },
+        // terminator
+        unsafe { core::mem::zeroed() },
+    ]);
+
+    static GATT_DEFS: Table<[ble_gatt_svc_def; 2]> = Table([
+        ble_gatt_svc_def {
+            type_: BLE_GATT_SVC_TYPE_PRIMARY as u8,
+            uuid: &SVC_UUID.u,
+            characteristics: GATT_CHRS.0.as_ptr(),
+            ..unsafe { core::mem::zeroed() }
+        },
+        unsafe { core::mem::zeroed() },
+    ]);
+}
@@ -0,0 +1,185 @@
+//! Tombol dengan debounce dan deteksi klik/dobel-klik/tekan lama, tanpa
+//! interrupt maupun delay: panggil `poll(now_ms)` secara berkala (≤ 10 ms).
+
+use embedded_hal::digital::InputPin;
+use heapless::Deque;
+
+use crate::led::Polarity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonEvent {
+    Press,
+    Release,
+    /// Tekan singkat; ditunda `double_click_ms` untuk menunggu klik kedua
+    Click,
+    DoubleClick,
+    /// Ditahan `long_press_ms`; tidak diikuti `Click` saat dilepas
+    LongPress,
+    /// Tiap `repeat_ms` setelah `LongPress` selama masih ditahan
+    Repeat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonConfig {
+    /// Level mentah harus stabil selama ini sebelum diterima
+    pub debounce_ms: u32,
+    /// Default active-low (tombol ke GND dengan pull-up)
+    pub polarity: Polarity,
+    /// 0 = tanpa dobel-klik, `Click` langsung saat dilepas
+    pub double_click_ms: u32,
+    pub long_press_ms: u32,
+    pub repeat_ms: Option<u32>,
+}
+
+impl Default for ButtonConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 20,
+            polarity: Polarity::ActiveLow,
+            double_click_ms: 300,
+            long_press_ms: 800,
+            repeat_ms: Some(200),
+        }
+    }
+}
+
+pub struct Button<PIN> {
+    pin: PIN,
+    config: ButtonConfig,
+
+    // level mentah terakhir dan sejak kapan stabil
+    raw: bool,
+    raw_since: u64,
+    pressed: bool,
+    pressed_at: u64,
+    long_fired: bool,
+    next_repeat: u64,
+
+    // klik singkat yang menunggu pasangan
+    clicks: u8,
+    released_at: u64,
+
+    events: Deque<ButtonEvent, 4>,
+}
+
+impl<PIN: InputPin> Button<PIN> {
+    pub fn new(pin: PIN, config: ButtonConfig) -> Self {
+        Self {
+            pin,
+            config,
+            raw: false,
+            raw_since: 0,
+            pressed: false,
+            pressed_at: 0,
+            long_fired: false,
+            next_repeat: 0,
+            clicks: 0,
+            released_at: 0,
+            events: Deque::new(),
+        }
+    }
+
+    pub fn config(&self) -> &ButtonConfig {
+        &self.config
+    }
+
+    /// State setelah debounce
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Baca pin lalu kembalikan satu event. Satu poll bisa menghasilkan
+    /// beberapa event (mis. `Release` lalu `Click`); panggil lagi sampai `None`.
+    pub fn poll(&mut self, now_ms: u64) -> Result<Option<ButtonEvent>, PIN::Error> {
+        if let Some(event) = self.events.pop_front() {
+            return Ok(Some(event));
+        }
+
+        let active = self.pin.is_high()? == (self.config.polarity == Polarity::ActiveHigh);
+        if active != self.raw {
+            self.raw = active;
+            self.raw_since = now_ms;
+        }
+
+        if self.raw != self.pressed && now_ms.saturating_sub(self.raw_since) >= self.config.debounce_ms as u64 {
+            self.pressed = self.raw;
+            if self.pressed {
+                self.on_press(now_ms);
+            } else {
+                self.on_release(now_ms);
+            }
+        }
+
+        if self.pressed {
+            self.on_hold(now_ms);
+        } else if self.clicks == 1 && now_ms.saturating_sub(self.released_at) >= self.config.double_click_ms as u64 {
+            self.clicks = 0;
+            self.push(ButtonEvent::Click);
+        }
+
+        Ok(self.events.pop_front())
+    }
+
+    pub fn into_inner(self) -> PIN {
+        self.pin
+    }
+
+    // ───────── internal ─────────
+
+    fn on_press(&mut self, now_ms: u64) {
+        self.pressed_at = now_ms;
+        self.long_fired = false;
+        self.push(ButtonEvent::Press);
+    }
+
+    fn on_hold(&mut self, now_ms: u64) {
+        if !self.long_fired {
+            if now_ms.saturating_sub(self.pressed_at) >= self.config.long_press_ms as u64 {
+                self.long_fired = true;
+                // klik pertama tidak jadi dobel-klik
+                if self.clicks == 1 {
+                    self.clicks = 0;
+                    self.push(ButtonEvent::Click);
+                }
+                self.push(ButtonEvent::LongPress);
+                self.next_repeat = now_ms.saturating_add(self.config.repeat_ms.unwrap_or(0) as u64);
+            }
+            return;
+        }
+
+        if let Some(repeat_ms) = self.config.repeat_ms {
+            if now_ms >= self.next_repeat {
+                self.next_repeat = now_ms.saturating_add(repeat_ms.max(1) as u64);
+                self.push(ButtonEvent::Repeat);
+            }
+        }
+    }
+
+    fn on_release(&mut self, now_ms: u64) {
+        self.push(ButtonEvent::Release);
+        if self.long_fired {
+            return;
+        }
+
+        if self.config.double_click_ms == 0 {
+            self.push(ButtonEvent::Click);
+            return;
+        }
+
+        self.clicks += 1;
+        if self.clicks == 2 {
+            self.clicks = 0;
+            self.push(ButtonEvent::DoubleClick);
+        } else {
+            self.released_at = now_ms;
+        }
+    }
+
+    fn push(&mut self, event: ButtonEvent) {
+        // kapasitas cukup untuk event satu poll; yang lama dibuang bila tidak dibaca
+        if self.events.is_full() {
+            self.events.pop_front();
+        }
+        let _ = self.events.push_back(event);
+    }
+}
@@ -0,0 +1,91 @@
+//! Pemetaan karakter Unicode ke ROM karakter HD44780
+
+/// Varian ROM karakter controller
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CharRom {
+    /// Jepang (paling umum di modul murah): ASCII tanpa '\' dan '~', plus katakana
+    #[default]
+    A00,
+    /// Eropa: ASCII penuh, 0xA0..0xFF kurang lebih ISO-8859-1
+    A02,
+}
+
+/// Pengganti karakter yang tidak ada di ROM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharFallback {
+    /// Kode ROM yang ditulis bila tidak ada glyph
+    pub replacement: u8,
+    /// Glyph 5x8 yang di-upload otomatis ke CGRAM saat karakternya dipakai
+    pub glyphs: &'static [(char, [u8; 8])],
+}
+
+impl Default for CharFallback {
+    fn default() -> Self {
+        Self {
+            replacement: b'?',
+            glyphs: &[],
+        }
+    }
+}
+
+impl CharFallback {
+    pub fn glyph(&self, c: char) -> Option<&[u8; 8]> {
+        self.glyphs.iter().find(|(g, _)| *g == c).map(|(_, bitmap)| bitmap)
+    }
+}
+
+/// Kode ROM untuk `c`, `None` bila ROM tidak punya karakter itu
+pub fn rom_code(rom: CharRom, c: char) -> Option<u8> {
+    match rom {
+        CharRom::A00 => a00(c),
+        CharRom::A02 => a02(c),
+    }
+}
+
+fn a00(c: char) -> Option<u8> {
+    let code = match c {
+        // 0x5C = '¥' dan 0x7E = '→' di ROM ini
+        '\\' | '~' => return None,
+        ' '..='}' => c as u8,
+        '¥' => 0x5C,
+        '→' => 0x7E,
+        '←' => 0x7F,
+        // katakana half-width U+FF61..U+FF9F berurutan di 0xA1..0xDF
+        '\u{FF61}'..='\u{FF9F}' => (c as u32 - 0xFF61 + 0xA1) as u8,
+        '。' => 0xA1,
+        '「' => 0xA2,
+        '」' => 0xA3,
+        '、' => 0xA4,
+        '・' => 0xA5,
+        '°' => 0xDF,
+        'α' => 0xE0,
+        'ä' => 0xE1,
+        'β' => 0xE2,
+        'ε' => 0xE3,
+        'µ' | 'μ' => 0xE4,
+        'σ' => 0xE5,
+        'ρ' => 0xE6,
+        '√' => 0xE8,
+        '¢' => 0xEC,
+        'ñ' => 0xEE,
+        'ö' => 0xEF,
+        'θ' => 0xF2,
+        '∞' => 0xF3,
+        'Ω' => 0xF4,
+        'ü' => 0xF5,
+        'Σ' => 0xF6,
+        'π' => 0xF7,
+        '÷' => 0xFD,
+        '█' => 0xFF,
+        _ => return None,
+    };
+    Some(code)
+}
+
+fn a02(c: char) -> Option<u8> {
+    match c {
+        ' '..='~' | '\u{A0}'..='\u{FF}' => Some(c as u32 as u8),
+        'μ' => Some(0xB5),
+        _ => None,
+    }
+}
@@ -0,0 +1,165 @@
+//! Warna RGB/HSV dan efek untuk LED addressable; semua integer agar murah di MCU.
+
+use crate::led;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+    pub const RED: Rgb = Rgb::new(255, 0, 0);
+    pub const GREEN: Rgb = Rgb::new(0, 255, 0);
+    pub const BLUE: Rgb = Rgb::new(0, 0, 255);
+    pub const AMBER: Rgb = Rgb::new(255, 120, 0);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Kalikan tiap kanal dengan `level / 255`
+    pub fn scale(self, level: u8) -> Self {
+        let f = |c: u8| ((c as u16 * (level as u16 + 1)) >> 8) as u8;
+        Self::new(f(self.r), f(self.g), f(self.b))
+    }
+
+    /// Koreksi gamma per kanal (kurva sama dengan `Led` PWM)
+    pub fn gamma(self) -> Self {
+        Self::new(led::gamma(self.r), led::gamma(self.g), led::gamma(self.b))
+    }
+
+    /// Interpolasi linear: `t` = 0 memberi `self`, 255 memberi `to`
+    pub fn lerp(self, to: Rgb, t: u8) -> Self {
+        let f = |a: u8, b: u8| (a as i32 + (b as i32 - a as i32) * t as i32 / 255) as u8;
+        Self::new(f(self.r, to.r), f(self.g, to.g), f(self.b, to.b))
+    }
+}
+
+/// HSV 8-bit: `h` satu putaran penuh dalam 0..=255
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Hsv {
+    pub h: u8,
+    pub s: u8,
+    pub v: u8,
+}
+
+impl Hsv {
+    pub const fn new(h: u8, s: u8, v: u8) -> Self {
+        Self { h, s, v }
+    }
+}
+
+impl From<Hsv> for Rgb {
+    fn from(hsv: Hsv) -> Self {
+        let (h, s, v) = (hsv.h as u16, hsv.s as u16, hsv.v as u16);
+        if s == 0 {
+            return Rgb::new(hsv.v, hsv.v, hsv.v);
+        }
+
+        // enam sektor @43
+        let region = h / 43;
+        let rem = (h - region * 43) * 6;
+        let p = (v * (255 - s)) >> 8;
+        let q = (v * (255 - ((s * rem) >> 8))) >> 8;
+        let t = (v * (255 - ((s * (255 - rem)) >> 8))) >> 8;
+
+        let (r, g, b) = match region {
+            0 => (v, t, p),
+            1 => (q, v, p),
+            2 => (p, v, t),
+            3 => (p, q, v),
+            4 => (t, p, v),
+            _ => (v, p, q),
+        };
+        Rgb::new(r as u8, g as u8, b as u8)
+    }
+}
+
+// ───────── efek ─────────
+
+/// Efek animasi; `render` menggambar satu frame pada waktu `t` ms sejak mulai
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Solid(Rgb),
+    /// Pelangi berjalan: satu putaran hue per `period_ms`, tersebar sepanjang strip
+    Rainbow { period_ms: u32 },
+    /// Satu titik berjalan tiap `step_ms` dengan ekor `tail` LED yang meredup
+    Chase { color: Rgb, step_ms: u32, tail: u8 },
+    /// Peralihan `from` ke `to` selama `duration_ms`, lalu tetap `to`
+    Fade { from: Rgb, to: Rgb, duration_ms: u32 },
+    /// Terang-redup satu warna, satu siklus per `period_ms`
+    Pulse { color: Rgb, period_ms: u32 },
+}
+
+impl Effect {
+    pub fn render(&self, t: u64, pixels: &mut [Rgb]) {
+        let len = pixels.len().max(1) as u64;
+        match *self {
+            Effect::Solid(color) => pixels.fill(color),
+            Effect::Rainbow { period_ms } => {
+                let base = t % period_ms.max(1) as u64 * 256 / period_ms.max(1) as u64;
+                for (i, px) in pixels.iter_mut().enumerate() {
+                    let h = (base + i as u64 * 256 / len) as u8;
+                    *px = Hsv::new(h, 255, 255).into();
+                }
+            }
+            Effect::Chase { color, step_ms, tail } => {
+                let head = t / step_ms.max(1) as u64 % len;
+                for (i, px) in pixels.iter_mut().enumerate() {
+                    // jarak di belakang kepala, melingkar
+                    let behind = (head + len - i as u64) % len;
+                    *px = if behind <= tail as u64 {
+                        color.scale((255 - behind * 255 / (tail as u64 + 1)) as u8)
+                    } else {
+                        Rgb::BLACK
+                    };
+                }
+            }
+            Effect::Fade { from, to, duration_ms } => {
+                let t = (t * 255 / duration_ms.max(1) as u64).min(255) as u8;
+                pixels.fill(from.lerp(to, t));
+            }
+            Effect::Pulse { color, period_ms } => {
+                let period = (period_ms as u64).max(2);
+                let (half, phase) = (period / 2, t % period);
+                let rise = if phase < half { phase } else { period - phase };
+                pixels.fill(color.scale((rise * 255 / half).min(255) as u8));
+            }
+        }
+    }
+}
+
+/// Warna status standar untuk LED indikator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Busy,
+    Warning,
+    Error,
+}
+
+impl Status {
+    pub fn color(self) -> Rgb {
+        match self {
+            Status::Ok => Rgb::GREEN,
+            Status::Busy => Rgb::BLUE,
+            Status::Warning => Rgb::AMBER,
+            Status::Error => Rgb::RED,
+        }
+    }
+
+    /// Ok diam, Busy berputar, Warning berdenyut pelan, Error berdenyut cepat
+    pub fn effect(self) -> Effect {
+        let color = self.color();
+        match self {
+            Status::Ok => Effect::Solid(color),
+            Status::Busy => Effect::Chase { color, step_ms: 80, tail: 2 },
+            Status::Warning => Effect::Pulse { color, period_ms: 2000 },
+            Status::Error => Effect::Pulse { color, period_ms: 500 },
+        }
+    }
+}
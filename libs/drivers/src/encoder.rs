@@ -0,0 +1,201 @@
+//! Rotary encoder kuadratur (mis. KY-040) lewat polling dua pin.
+//!
+//! Tiap transisi valid A/B dihitung ±1 lewat tabel; transisi tidak valid
+//! (kedua pin berubah sekaligus) bernilai 0. Langkah hanya dikeluarkan saat
+//! kembali ke posisi detent, jadi pantulan kontak saling meniadakan.
+
+use embedded_hal::digital::{ErrorType, InputPin};
+
+use crate::button::{Button, ButtonConfig, ButtonEvent};
+
+/// Indeks: (state lama << 2) | state baru, state = (A << 1) | B
+const TRANSITIONS: [i8; 16] = [0, -1, 1, 0, 1, 0, 0, -1, -1, 0, 0, 1, 0, 1, -1, 0];
+
+/// Percepatan: putaran cepat memberi lebih dari satu langkah per detent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Acceleration {
+    /// Jarak antar detent ≥ ini: 1 langkah
+    pub slow_ms: u32,
+    /// Jarak antar detent ≤ ini: `max_step` langkah
+    pub fast_ms: u32,
+    pub max_step: u8,
+}
+
+impl Default for Acceleration {
+    fn default() -> Self {
+        Self {
+            slow_ms: 120,
+            fast_ms: 20,
+            max_step: 8,
+        }
+    }
+}
+
+impl Acceleration {
+    /// Langkah per detent untuk jarak `dt_ms` dari detent sebelumnya
+    pub fn multiplier(&self, dt_ms: u64) -> i32 {
+        let (slow, fast, max) = (self.slow_ms as u64, self.fast_ms as u64, self.max_step.max(1) as u64);
+        if dt_ms >= slow {
+            1
+        } else if dt_ms <= fast || slow <= fast {
+            max as i32
+        } else {
+            (1 + (max - 1) * (slow - dt_ms) / (slow - fast)) as i32
+        }
+    }
+}
+
+/// Transisi kuadratur per detent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StepsPerDetent {
+    One = 1,
+    Two = 2,
+    /// KY-040 umum
+    #[default]
+    Four = 4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncoderConfig {
+    pub steps_per_detent: StepsPerDetent,
+    pub acceleration: Option<Acceleration>,
+    /// Balik arah (A mendahului B = +1 tanpa ini)
+    pub reverse: bool,
+}
+
+impl Default for EncoderConfig {
+    fn default() -> Self {
+        Self {
+            steps_per_detent: StepsPerDetent::Four,
+            acceleration: None,
+            reverse: false,
+        }
+    }
+}
+
+/// Decoder kuadratur tanpa tombol
+pub struct Quadrature<A, B> {
+    a: A,
+    b: B,
+    config: EncoderConfig,
+    // None sebelum poll pertama; state awal dianggap posisi detent
+    state: Option<u8>,
+    rest: u8,
+    accum: i8,
+    last_detent: Option<(u64, i32)>,
+}
+
+impl<A, B, E> Quadrature<A, B>
+where
+    A: InputPin + ErrorType<Error = E>,
+    B: InputPin + ErrorType<Error = E>,
+{
+    pub fn new(a: A, b: B, config: EncoderConfig) -> Self {
+        Self {
+            a,
+            b,
+            config,
+            state: None,
+            rest: 0,
+            accum: 0,
+            last_detent: None,
+        }
+    }
+
+    pub fn config(&self) -> &EncoderConfig {
+        &self.config
+    }
+
+    /// Baca pin; kembalikan langkah (sudah dikali percepatan) sejak poll sebelumnya
+    pub fn poll(&mut self, now_ms: u64) -> Result<i32, E> {
+        let state = ((self.a.is_high()? as u8) << 1) | self.b.is_high()? as u8;
+        let Some(prev) = self.state.replace(state) else {
+            self.rest = state;
+            return Ok(0);
+        };
+        if prev == state {
+            return Ok(0);
+        }
+
+        self.accum += TRANSITIONS[((prev << 2) | state) as usize];
+        if !self.is_rest(state) {
+            return Ok(0);
+        }
+
+        // di detent: cukup transisi = satu langkah, sisanya (setengah putaran, pantulan) dibuang
+        let accum = core::mem::take(&mut self.accum);
+        let steps = self.config.steps_per_detent as i8;
+        if accum.abs() < steps {
+            return Ok(0);
+        }
+
+        let mut dir = accum.signum() as i32;
+        if self.config.reverse {
+            dir = -dir;
+        }
+        Ok(dir * self.multiplier(now_ms, dir))
+    }
+
+    fn is_rest(&self, state: u8) -> bool {
+        match self.config.steps_per_detent {
+            StepsPerDetent::One => true,
+            // 00 dan 11, atau 01 dan 10
+            StepsPerDetent::Two => (state == 0 || state == 3) == (self.rest == 0 || self.rest == 3),
+            StepsPerDetent::Four => state == self.rest,
+        }
+    }
+
+    fn multiplier(&mut self, now_ms: u64, dir: i32) -> i32 {
+        let last = self.last_detent.replace((now_ms, dir));
+        let Some(accel) = self.config.acceleration else {
+            return 1;
+        };
+        match last {
+            // ganti arah selalu mulai pelan
+            Some((at, last_dir)) if last_dir == dir => accel.multiplier(now_ms.saturating_sub(at)),
+            _ => 1,
+        }
+    }
+}
+
+// ───────── encoder + tombol ─────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncoderEvent {
+    /// Langkah putaran (positif searah jarum jam)
+    Turn(i32),
+    Button(ButtonEvent),
+}
+
+/// Encoder dengan tombol tekan terintegrasi (pin SW KY-040)
+pub struct Encoder<A, B, SW> {
+    quadrature: Quadrature<A, B>,
+    button: Button<SW>,
+}
+
+impl<A, B, SW, E> Encoder<A, B, SW>
+where
+    A: InputPin + ErrorType<Error = E>,
+    B: InputPin + ErrorType<Error = E>,
+    SW: InputPin + ErrorType<Error = E>,
+{
+    pub fn new(a: A, b: B, sw: SW, config: EncoderConfig, button: ButtonConfig) -> Self {
+        Self {
+            quadrature: Quadrature::new(a, b, config),
+            button: Button::new(sw, button),
+        }
+    }
+
+    /// Putaran didahulukan; panggil lagi sampai `None` untuk menguras event tombol
+    pub fn poll(&mut self, now_ms: u64) -> Result<Option<EncoderEvent>, E> {
+        let steps = self.quadrature.poll(now_ms)?;
+        if steps != 0 {
+            return Ok(Some(EncoderEvent::Turn(steps)));
+        }
+        Ok(self.button.poll(now_ms)?.map(EncoderEvent::Button))
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        self.button.is_pressed()
+    }
+}
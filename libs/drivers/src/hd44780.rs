@@ -0,0 +1,483 @@
+//! Encoding perintah HD44780 lewat backpack I2C (wiring: `Backpack`).
+//! `LcdCore` menyimpan state controller dan menerjemahkan tiap operasi ke
+//! langkah I/O (`Io`); `LcdI2c` (blocking) dan `LcdI2cAsync` hanya menjalankannya.
+
+use heapless::{Deque, Vec};
+
+use crate::backpack::Backpack;
+use crate::charset::{self, CharFallback, CharRom};
+use crate::lcd_i2c::{LcdError, LcdGeometry, WaitMode, CGRAM_SLOTS};
+
+// ───────── instruction set ─────────
+
+pub const CLEAR: u8 = 0x01;
+pub const HOME: u8 = 0x02;
+pub const ENTRY_MODE: u8 = 0x04;
+pub const ENTRY_INCREMENT: u8 = 0x02;
+pub const DISPLAY_CONTROL: u8 = 0x08;
+pub const DISPLAY_ON: u8 = 0x04;
+pub const CURSOR_ON: u8 = 0x02;
+pub const BLINK_ON: u8 = 0x01;
+pub const CURSOR_SHIFT: u8 = 0x10;
+pub const DISPLAY_SHIFT: u8 = 0x08;
+pub const SHIFT_RIGHT: u8 = 0x04;
+pub const FUNCTION_SET: u8 = 0x20;
+pub const TWO_LINE: u8 = 0x08;
+pub const SET_CGRAM: u8 = 0x40;
+pub const SET_DDRAM: u8 = 0x80;
+
+// ───────── timing ─────────
+
+/// Lebar pulsa EN dan waktu eksekusi per nibble
+pub(crate) const EN_PULSE_NS: u32 = 1_000;
+pub(crate) const NIBBLE_SETTLE_NS: u32 = 50_000;
+/// Clear dan home butuh ~1.52 ms
+pub(crate) const CLEAR_NS: u32 = 2_000_000;
+
+/// Langkah init 4-bit (datasheet HD44780 fig. 24), sebelum display control dan clear
+const INIT: [Instr; 11] = [
+    Instr::DelayUs(50_000),
+    Instr::Nibble(0x03),
+    Instr::DelayUs(5_000),
+    Instr::Nibble(0x03),
+    Instr::DelayUs(150),
+    Instr::Nibble(0x03),
+    Instr::DelayUs(150),
+    Instr::Nibble(0x02),
+    Instr::DelayUs(150),
+    Instr::Command(FUNCTION_SET | TWO_LINE),
+    Instr::Command(ENTRY_MODE | ENTRY_INCREMENT),
+];
+
+/// Perintah shift seluruh display satu kolom
+pub(crate) fn shift_command(right: bool) -> u8 {
+    CURSOR_SHIFT | DISPLAY_SHIFT | if right { SHIFT_RIGHT } else { 0 }
+}
+
+/// Perintah set DDRAM address untuk (col, row), `None` bila di luar panel
+pub(crate) fn ddram_command(geometry: LcdGeometry, col: u8, row: u8) -> Option<u8> {
+    if col >= geometry.cols || row >= geometry.rows {
+        return None;
+    }
+    Some(SET_DDRAM | (col + geometry.row_offset(row)))
+}
+
+/// Perintah set CGRAM address untuk glyph `slot`
+pub(crate) fn cgram_command(slot: u8) -> Option<u8> {
+    (slot < CGRAM_SLOTS).then_some(SET_CGRAM | (slot << 3))
+}
+
+/// Alamat DDRAM setelah satu tulis data (mode 2 baris: 0x27 -> 0x40 -> 0x67 -> 0x00)
+pub(crate) fn next_ddram(addr: u8) -> u8 {
+    match addr {
+        0x27 => 0x40,
+        0x67.. => 0x00,
+        a => a + 1,
+    }
+}
+
+// ───────── CGRAM ─────────
+
+/// Identitas glyph di CGRAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphId {
+    /// Fallback karakter Unicode (lihat `CharFallback`)
+    Char(char),
+    /// Icon bernama dari layer service
+    Named(&'static str),
+    /// Di-upload langsung lewat `create_char`
+    Slot(u8),
+}
+
+/// Isi ke-8 slot CGRAM, disimpan agar bisa di-upload ulang setelah init
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct CgramTable {
+    slots: [Option<(GlyphId, [u8; 8])>; CGRAM_SLOTS as usize],
+}
+
+impl CgramTable {
+    pub fn find(&self, id: GlyphId) -> Option<u8> {
+        self.slots
+            .iter()
+            .position(|s| matches!(s, Some((g, _)) if *g == id))
+            .map(|i| i as u8)
+    }
+
+    pub fn free_slot(&self) -> Option<u8> {
+        self.slots.iter().position(Option::is_none).map(|i| i as u8)
+    }
+
+    pub fn set(&mut self, slot: u8, id: GlyphId, bitmap: [u8; 8]) {
+        // slot raw menimpa glyph apa pun yang ada di sana
+        self.slots[slot as usize] = Some((id, bitmap));
+    }
+
+    pub fn release(&mut self, id: GlyphId) {
+        for s in self.slots.iter_mut() {
+            if matches!(s, Some((g, _)) if *g == id) {
+                *s = None;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.slots = Default::default();
+    }
+
+    pub fn bitmap(&self, slot: u8) -> Option<[u8; 8]> {
+        self.slots[slot as usize].map(|(_, bitmap)| bitmap)
+    }
+}
+
+// ───────── sans-IO core ─────────
+
+/// Satu transaksi tulis I2C (1-2 byte)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Frame {
+    buf: [u8; 2],
+    len: u8,
+}
+
+impl Frame {
+    pub fn bytes(&self) -> &[u8] {
+        &self.buf[..self.len as usize]
+    }
+}
+
+/// Langkah I/O primitif yang dijalankan driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Io {
+    Write(Frame),
+    /// Baca satu byte port; hasilnya diberikan ke `LcdCore::next` berikutnya
+    Read,
+    DelayUs(u32),
+    DelayNs(u32),
+}
+
+/// Instruksi tingkat controller; satu operasi = urutan instruksi
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Instr {
+    /// Konfigurasi expander (arah pin MCP23008)
+    Setup,
+    /// Nilai port apa adanya (backlight)
+    Port(u8),
+    /// Satu nibble RS=0 tanpa menunggu busy (init)
+    Nibble(u8),
+    Command(u8),
+    Data(u8),
+    DelayUs(u32),
+    /// Delay hanya di mode `Timed`
+    Settle(u32),
+    /// Paksa mode `Timed` (true) atau kembali ke mode yang dikonfigurasi (false)
+    ForceTimed(bool),
+    /// Perubahan state setelah semua instruksi sebelumnya berhasil
+    Commit(Effect),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Effect {
+    Ddram(u8),
+    Control(u8),
+    Glyph(u8, GlyphId, [u8; 8]),
+}
+
+/// Instruksi untuk operasi pendek (maks. upload satu glyph)
+pub(crate) type Plan = Vec<Instr, 12>;
+
+/// Langkah I/O yang tersisa dari satu instruksi
+#[derive(Default)]
+pub(crate) struct Exec {
+    queue: Deque<Io, 8>,
+    /// Sisa baca busy flag; `None` = tidak menunggu
+    polls: Option<u16>,
+    polled: bool,
+    busy: bool,
+}
+
+/// State controller HD44780 di belakang backpack, tanpa I/O
+pub(crate) struct LcdCore {
+    pub geometry: LcdGeometry,
+    pub backpack: Backpack,
+    pub backlight: bool,
+    /// Mode tunggu yang dikonfigurasi
+    wait: WaitMode,
+    /// Pakai delay tetap sementara: init berjalan atau busy flag timeout.
+    /// Mode yang dikonfigurasi (`wait`) tidak berubah.
+    timed: bool,
+    // flag DISPLAY_CONTROL (display/cursor/blink)
+    pub control: u8,
+    pub rom: CharRom,
+    pub fallback: CharFallback,
+    pub cgram: CgramTable,
+    // posisi address counter di DDRAM, dipulihkan setelah upload CGRAM
+    ddram: u8,
+}
+
+impl LcdCore {
+    pub fn new(geometry: LcdGeometry, backpack: Backpack) -> Self {
+        Self {
+            geometry,
+            backpack,
+            backlight: true,
+            wait: WaitMode::Timed,
+            timed: false,
+            control: DISPLAY_ON,
+            rom: CharRom::default(),
+            fallback: CharFallback::default(),
+            cgram: CgramTable::default(),
+            ddram: 0,
+        }
+    }
+
+    // ───────── operasi ─────────
+
+    /// Init 4-bit, display control, upload ulang glyph lalu clear.
+    /// Kembali ke mode tunggu yang dikonfigurasi (busy flag dicoba lagi).
+    pub fn init(&self) -> impl Iterator<Item = Instr> {
+        let (cgram, ddram) = (self.cgram, self.ddram);
+        [Instr::Setup, Instr::ForceTimed(true)]
+            .into_iter()
+            .chain(INIT)
+            .chain([
+                Instr::ForceTimed(false),
+                Instr::Command(DISPLAY_CONTROL | self.control),
+            ])
+            .chain(
+                (0..CGRAM_SLOTS)
+                    .filter_map(move |slot| Some((cgram_command(slot)?, cgram.bitmap(slot)?)))
+                    .flat_map(move |(cmd, bitmap)| upload(cmd, &bitmap, ddram)),
+            )
+            .chain(self.clear())
+    }
+
+    pub fn clear(&self) -> Plan {
+        plan(&[
+            Instr::Command(CLEAR),
+            Instr::Settle(CLEAR_NS),
+            Instr::Commit(Effect::Ddram(0)),
+        ])
+    }
+
+    pub fn home(&self) -> Plan {
+        plan(&[
+            Instr::Command(HOME),
+            Instr::Settle(CLEAR_NS),
+            Instr::Commit(Effect::Ddram(0)),
+        ])
+    }
+
+    pub fn set_cursor<E>(&self, col: u8, row: u8) -> Result<Plan, LcdError<E>> {
+        let cmd = ddram_command(self.geometry, col, row).ok_or(LcdError::InvalidPosition)?;
+        Ok(plan(&[
+            Instr::Command(cmd),
+            Instr::Commit(Effect::Ddram(cmd & !SET_DDRAM)),
+        ]))
+    }
+
+    pub fn set_backlight(&mut self, on: bool) -> Plan {
+        self.backlight = on;
+        plan(&[Instr::Port(self.backpack.idle(on))])
+    }
+
+    pub fn set_control(&self, flag: u8, on: bool) -> Plan {
+        let control = if on {
+            self.control | flag
+        } else {
+            self.control & !flag
+        };
+        plan(&[
+            Instr::Command(DISPLAY_CONTROL | control),
+            Instr::Commit(Effect::Control(control)),
+        ])
+    }
+
+    pub fn scroll(&self, right: bool) -> Plan {
+        plan(&[Instr::Command(shift_command(right))])
+    }
+
+    pub fn write_char(&self, code: u8) -> Plan {
+        plan(&[
+            Instr::Data(code),
+            Instr::Commit(Effect::Ddram(next_ddram(self.ddram))),
+        ])
+    }
+
+    /// Upload glyph ke `slot`, menimpa glyph terdaftar di slot itu
+    pub fn create_char<E>(&self, slot: u8, bitmap: &[u8; 8]) -> Result<Plan, LcdError<E>> {
+        let cmd = cgram_command(slot).ok_or(LcdError::InvalidSlot)?;
+        let mut plan: Plan = upload(cmd, bitmap, self.ddram).collect();
+        let _ = plan.push(Instr::Commit(Effect::Glyph(
+            slot,
+            GlyphId::Slot(slot),
+            *bitmap,
+        )));
+        Ok(plan)
+    }
+
+    /// Slot untuk glyph `id` dan upload yang diperlukan (kosong bila sudah ada)
+    pub fn load_glyph<E>(&self, id: GlyphId, bitmap: &[u8; 8]) -> Result<(u8, Plan), LcdError<E>> {
+        if let Some(slot) = self.cgram.find(id) {
+            return Ok((slot, Plan::new()));
+        }
+
+        let slot = self.cgram.free_slot().ok_or(LcdError::CgramFull)?;
+        let cmd = cgram_command(slot).ok_or(LcdError::InvalidSlot)?;
+        let mut plan: Plan = upload(cmd, bitmap, self.ddram).collect();
+        let _ = plan.push(Instr::Commit(Effect::Glyph(slot, id, *bitmap)));
+        Ok((slot, plan))
+    }
+
+    /// Kode karakter untuk `c`: ROM, lalu glyph fallback di CGRAM
+    /// (di-upload bila perlu), lalu karakter pengganti.
+    pub fn encode(&self, c: char) -> (u8, Plan) {
+        if let Some(code) = charset::rom_code(self.rom, c) {
+            return (code, Plan::new());
+        }
+
+        let replacement = (self.fallback.replacement, Plan::new());
+        match self.fallback.glyph(c) {
+            Some(bitmap) => self
+                .load_glyph::<()>(GlyphId::Char(c), bitmap)
+                .unwrap_or(replacement),
+            None => replacement,
+        }
+    }
+
+    // ───────── mode tunggu ─────────
+
+    /// `BusyFlag` diabaikan bila backpack tidak bisa dibaca
+    pub fn set_wait_mode(&mut self, wait: WaitMode) {
+        let readable = self.backpack.read_frames(self.backlight).is_some();
+        self.wait = match wait {
+            WaitMode::BusyFlag { .. } if !readable => WaitMode::Timed,
+            other => other,
+        };
+        self.timed = false;
+    }
+
+    /// Mode yang sedang dipakai; `Timed` setelah busy flag timeout sampai init berikutnya
+    pub fn wait_mode(&self) -> WaitMode {
+        if self.timed {
+            WaitMode::Timed
+        } else {
+            self.wait
+        }
+    }
+
+    // ───────── eksekusi ─────────
+
+    /// Ubah satu instruksi ke langkah I/O
+    pub fn begin(&mut self, instr: Instr) -> Exec {
+        let mut exec = Exec::default();
+        match instr {
+            Instr::Setup => {
+                if let Some(buf) = self.backpack.setup() {
+                    exec.push(Io::Write(Frame { buf, len: 2 }));
+                }
+            }
+            Instr::Port(port) => exec.push(self.port(port)),
+            Instr::Nibble(nibble) => self.nibble(&mut exec, nibble, false),
+            Instr::Command(byte) | Instr::Data(byte) => {
+                let rs = matches!(instr, Instr::Data(_));
+                self.nibble(&mut exec, byte >> 4, rs);
+                self.nibble(&mut exec, byte & 0x0F, rs);
+                if let WaitMode::BusyFlag { max_polls } = self.wait_mode() {
+                    exec.polls = Some(max_polls);
+                }
+            }
+            Instr::DelayUs(us) => exec.push(Io::DelayUs(us)),
+            Instr::Settle(ns) => self.settle(&mut exec, ns),
+            Instr::ForceTimed(on) => self.timed = on,
+            Instr::Commit(effect) => match effect {
+                Effect::Ddram(addr) => self.ddram = addr,
+                Effect::Control(control) => self.control = control,
+                Effect::Glyph(slot, id, bitmap) => self.cgram.set(slot, id, bitmap),
+            },
+        }
+        exec
+    }
+
+    /// Langkah berikutnya; `read` = hasil `Io::Read` terakhir
+    pub fn next(&mut self, exec: &mut Exec, read: Option<u8>) -> Option<Io> {
+        if let Some(port) = read {
+            exec.busy = self.backpack.busy(port);
+        }
+        if let Some(io) = exec.queue.pop_front() {
+            return Some(io);
+        }
+
+        let left = exec.polls?;
+        if exec.polled && !exec.busy {
+            exec.polls = None;
+            return None;
+        }
+        if left == 0 {
+            // busy flag tidak pernah turun (RW tidak tersambung?): delay tetap sampai init berikutnya
+            exec.polls = None;
+            self.timed = true;
+            return Some(Io::DelayNs(CLEAR_NS));
+        }
+
+        exec.polls = Some(left - 1);
+        exec.polled = true;
+        self.read_busy(exec);
+        exec.queue.pop_front()
+    }
+
+    fn nibble(&self, exec: &mut Exec, nibble: u8, rs: bool) {
+        let [high, low] = self.backpack.nibble_frames(nibble, rs, self.backlight);
+        exec.push(self.port(high));
+        exec.push(Io::DelayNs(EN_PULSE_NS));
+        exec.push(self.port(low));
+        self.settle(exec, NIBBLE_SETTLE_NS);
+    }
+
+    fn settle(&self, exec: &mut Exec, ns: u32) {
+        if self.wait_mode() == WaitMode::Timed {
+            exec.push(Io::DelayNs(ns));
+        }
+    }
+
+    /// Baca BF (nibble tinggi); nibble rendah tetap di-clock agar urutan 4-bit terjaga
+    fn read_busy(&self, exec: &mut Exec) {
+        let Some([high, low]) = self.backpack.read_frames(self.backlight) else {
+            return;
+        };
+        for io in [
+            self.port(low),
+            self.port(high),
+            Io::Read,
+            self.port(low),
+            self.port(high),
+            self.port(low),
+        ] {
+            exec.push(io);
+        }
+    }
+
+    fn port(&self, port: u8) -> Io {
+        let mut buf = [0; 2];
+        let len = self.backpack.port_write(port, &mut buf).len() as u8;
+        Io::Write(Frame { buf, len })
+    }
+}
+
+impl Exec {
+    fn push(&mut self, io: Io) {
+        // kapasitas cukup untuk dua nibble atau satu baca busy flag
+        let _ = self.queue.push_back(io);
+    }
+}
+
+fn plan(instrs: &[Instr]) -> Plan {
+    Vec::from_slice(instrs).unwrap_or_default()
+}
+
+/// Upload bitmap lewat `cgram_cmd` lalu kembalikan address counter ke DDRAM
+fn upload(cgram_cmd: u8, bitmap: &[u8; 8], ddram: u8) -> impl Iterator<Item = Instr> {
+    let bitmap = *bitmap;
+    [Instr::Command(cgram_cmd)]
+        .into_iter()
+        .chain(bitmap.into_iter().map(|row| Instr::Data(row & 0x1F)))
+        .chain([Instr::Command(SET_DDRAM | ddram)])
+}
@@ -0,0 +1,317 @@
+#![allow(dead_code)]
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c};
+use embedded_hal::delay::DelayNs;
+
+use crate::backpack::Backpack;
+use crate::charset::{CharFallback, CharRom};
+use crate::hd44780::{self, GlyphId, Instr, Io, LcdCore};
+
+/// Error driver LCD, generik atas error bus I2C
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LcdError<E> {
+    /// Backpack tidak menjawab (dicabut / alamat salah)
+    Nack(E),
+    /// Error bus lain (arbitration, overrun, ...)
+    Bus(E),
+    /// Kolom/baris di luar geometri panel
+    InvalidPosition,
+    /// Slot CGRAM di luar 0..8
+    InvalidSlot,
+    /// Ke-8 slot CGRAM sudah terpakai
+    CgramFull,
+}
+
+impl<E: embedded_hal::i2c::Error> LcdError<E> {
+    pub(crate) fn from_i2c(err: E) -> Self {
+        match err.kind() {
+            ErrorKind::NoAcknowledge(_) => LcdError::Nack(err),
+            _ => LcdError::Bus(err),
+        }
+    }
+}
+
+impl<E: embedded_hal::i2c::Error> embedded_hal::i2c::Error for LcdError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            LcdError::Nack(e) | LcdError::Bus(e) => e.kind(),
+            LcdError::InvalidPosition | LcdError::InvalidSlot | LcdError::CgramFull => ErrorKind::Other,
+        }
+    }
+}
+
+pub(crate) type LcdResult<T, I2C> = Result<T, LcdError<<I2C as ErrorType>::Error>>;
+
+/// Jumlah glyph user-defined di CGRAM HD44780
+pub const CGRAM_SLOTS: u8 = 8;
+
+/// Ukuran panel karakter (kolom x baris)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LcdGeometry {
+    pub cols: u8,
+    pub rows: u8,
+}
+
+impl LcdGeometry {
+    pub const LCD_16X2: Self = Self { cols: 16, rows: 2 };
+    pub const LCD_20X4: Self = Self { cols: 20, rows: 4 };
+    pub const LCD_16X4: Self = Self { cols: 16, rows: 4 };
+    pub const LCD_40X2: Self = Self { cols: 40, rows: 2 };
+
+    /// Alamat DDRAM awal tiap baris.
+    /// Baris 2 dan 3 adalah lanjutan baris 0 dan 1 (offset = jumlah kolom).
+    pub fn row_offset(&self, row: u8) -> u8 {
+        match row {
+            0 => 0x00,
+            1 => 0x40,
+            2 => self.cols,
+            _ => 0x40 + self.cols,
+        }
+    }
+}
+
+/// Cara menunggu controller selesai mengeksekusi instruksi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaitMode {
+    /// Delay tetap per nibble dan setelah clear/home (aman untuk semua modul)
+    #[default]
+    Timed,
+    /// Baca busy flag lewat RW. Bila masih busy setelah `max_polls` baca,
+    /// driver kembali ke `Timed` (mis. RW tidak tersambung di backpack).
+    BusyFlag { max_polls: u16 },
+}
+
+pub struct LcdI2c<I2C> {
+    i2c: I2C,
+    addr: u8,
+    core: LcdCore,
+}
+
+impl<I2C> LcdI2c<I2C>
+where
+    I2C: I2c,
+{
+    pub fn new<D>(i2c: I2C, addr: u8, geometry: LcdGeometry, delay: &mut D) -> LcdResult<Self, I2C>
+    where
+        D: DelayNs,
+    {
+        Self::with_backpack(i2c, addr, geometry, Backpack::default(), delay)
+    }
+
+    /// Simpan bus tanpa I/O; `init` wajib dipanggil sebelum dipakai.
+    /// Cocok untuk LCD yang mungkin belum terpasang saat boot.
+    pub fn uninit(i2c: I2C, addr: u8, geometry: LcdGeometry, backpack: Backpack) -> Self {
+        Self {
+            i2c,
+            addr,
+            core: LcdCore::new(geometry, backpack),
+        }
+    }
+
+    /// Seperti `new`, untuk backpack dengan wiring lain
+    pub fn with_backpack<D>(
+        i2c: I2C,
+        addr: u8,
+        geometry: LcdGeometry,
+        backpack: Backpack,
+        delay: &mut D,
+    ) -> LcdResult<Self, I2C>
+    where
+        D: DelayNs,
+    {
+        let mut lcd = Self::uninit(i2c, addr, geometry, backpack);
+        lcd.init(delay)?;
+        Ok(lcd)
+    }
+
+    /// Init sequence (4-bit mode); aman diulang setelah LCD dicabut-pasang.
+    /// Glyph yang sudah terdaftar di-upload ulang.
+    pub fn init<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        let plan = self.core.init();
+        self.run(plan, delay)
+    }
+
+    pub fn clear<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.clear(), delay)
+    }
+
+    pub fn backpack(&self) -> Backpack {
+        self.core.backpack
+    }
+
+    pub fn geometry(&self) -> LcdGeometry {
+        self.core.geometry
+    }
+
+    pub fn cols(&self) -> u8 {
+        self.core.geometry.cols
+    }
+
+    pub fn rows(&self) -> u8 {
+        self.core.geometry.rows
+    }
+
+    pub fn set_cursor<D: DelayNs>(&mut self, col: u8, row: u8, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.set_cursor(col, row)?, delay)
+    }
+
+    /// Tulis teks UTF-8; karakter dipetakan ke ROM (lihat `set_rom`/`set_fallback`)
+    pub fn print<D: DelayNs>(&mut self, text: &str, delay: &mut D) -> LcdResult<(), I2C> {
+        for c in text.chars() {
+            let code = self.encode(c, delay)?;
+            self.write_char(code, delay)?;
+        }
+        Ok(())
+    }
+
+    // ───────── display control ─────────
+
+    pub fn set_backlight(&mut self, on: bool) -> LcdResult<(), I2C> {
+        let plan = self.core.set_backlight(on);
+        self.run(plan, &mut NoDelay)
+    }
+
+    pub fn backlight(&self) -> bool {
+        self.core.backlight
+    }
+
+    /// Matikan tampilan tanpa menghapus isi DDRAM
+    pub fn set_display_on<D: DelayNs>(&mut self, on: bool, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.set_control(hd44780::DISPLAY_ON, on), delay)
+    }
+
+    pub fn display_on(&self) -> bool {
+        self.core.control & hd44780::DISPLAY_ON != 0
+    }
+
+    /// Kursor garis bawah di posisi address counter
+    pub fn set_cursor_visible<D: DelayNs>(&mut self, on: bool, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.set_control(hd44780::CURSOR_ON, on), delay)
+    }
+
+    pub fn cursor_visible(&self) -> bool {
+        self.core.control & hd44780::CURSOR_ON != 0
+    }
+
+    pub fn set_blink<D: DelayNs>(&mut self, on: bool, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.set_control(hd44780::BLINK_ON, on), delay)
+    }
+
+    pub fn blink(&self) -> bool {
+        self.core.control & hd44780::BLINK_ON != 0
+    }
+
+    /// Geser seluruh tampilan satu kolom ke kiri (isi DDRAM tetap)
+    pub fn scroll_left<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.scroll(false), delay)
+    }
+
+    pub fn scroll_right<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.scroll(true), delay)
+    }
+
+    /// Kursor ke (0, 0) dan batalkan semua shift
+    pub fn home<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.home(), delay)
+    }
+
+    /// Pilih mode tunggu. `BusyFlag` diabaikan bila backpack tidak bisa dibaca.
+    pub fn set_wait_mode(&mut self, wait: WaitMode) {
+        self.core.set_wait_mode(wait);
+    }
+
+    /// Mode tunggu aktif; `Timed` setelah busy flag timeout sampai `init` berikutnya
+    pub fn wait_mode(&self) -> WaitMode {
+        self.core.wait_mode()
+    }
+
+    // ───────── charset ─────────
+
+    /// ROM karakter yang terpasang di controller (tertulis di chip: ...A00 / ...A02)
+    pub fn set_rom(&mut self, rom: CharRom) {
+        self.core.rom = rom;
+    }
+
+    pub fn rom(&self) -> CharRom {
+        self.core.rom
+    }
+
+    pub fn set_fallback(&mut self, fallback: CharFallback) {
+        self.core.fallback = fallback;
+    }
+
+    pub fn fallback(&self) -> CharFallback {
+        self.core.fallback
+    }
+
+    /// Kode karakter untuk `c`: ROM, lalu glyph fallback di CGRAM
+    /// (di-upload bila perlu), lalu karakter pengganti.
+    pub fn encode<D: DelayNs>(&mut self, c: char, delay: &mut D) -> LcdResult<u8, I2C> {
+        let (code, plan) = self.core.encode(c);
+        self.run(plan, delay)?;
+        Ok(code)
+    }
+
+    // ───────── CGRAM ─────────
+
+    /// Upload glyph 5x8 ke CGRAM `slot` (0..8), menimpa glyph terdaftar di slot itu.
+    /// Posisi kursor tidak berubah.
+    pub fn create_char<D: DelayNs>(&mut self, slot: u8, bitmap: &[u8; 8], delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.create_char(slot, bitmap)?, delay)
+    }
+
+    /// Slot CGRAM untuk glyph `id`; upload ke slot kosong bila belum ada
+    pub fn load_glyph<D: DelayNs>(&mut self, id: GlyphId, bitmap: &[u8; 8], delay: &mut D) -> LcdResult<u8, I2C> {
+        let (slot, plan) = self.core.load_glyph(id, bitmap)?;
+        self.run(plan, delay)?;
+        Ok(slot)
+    }
+
+    pub fn glyph_slot(&self, id: GlyphId) -> Option<u8> {
+        self.core.cgram.find(id)
+    }
+
+    /// Bebaskan slot glyph; isi CGRAM tetap sampai slot dipakai lagi
+    pub fn release_glyph(&mut self, id: GlyphId) {
+        self.core.cgram.release(id);
+    }
+
+    pub fn clear_glyphs(&mut self) {
+        self.core.cgram.clear();
+    }
+
+    /// Tulis satu kode karakter di posisi kursor (0..8 = glyph CGRAM)
+    pub fn write_char<D: DelayNs>(&mut self, code: u8, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.write_char(code), delay)
+    }
+
+    // ───────── executor ─────────
+
+    /// Jalankan instruksi berurutan; berhenti di error I2C pertama
+    fn run<D: DelayNs>(&mut self, plan: impl IntoIterator<Item = Instr>, delay: &mut D) -> LcdResult<(), I2C> {
+        for instr in plan {
+            let mut exec = self.core.begin(instr);
+            let mut read = None;
+            while let Some(io) = self.core.next(&mut exec, read.take()) {
+                match io {
+                    Io::Write(frame) => self.i2c.write(self.addr, frame.bytes()).map_err(LcdError::from_i2c)?,
+                    Io::Read => {
+                        let mut buf = [0];
+                        self.i2c.read(self.addr, &mut buf).map_err(LcdError::from_i2c)?;
+                        read = Some(buf[0]);
+                    }
+                    Io::DelayUs(us) => delay.delay_us(us),
+                    Io::DelayNs(ns) => delay.delay_ns(ns),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Untuk operasi tanpa delay (tulis port backlight)
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
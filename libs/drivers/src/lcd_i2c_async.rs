@@ -0,0 +1,243 @@
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+use crate::backpack::Backpack;
+use crate::charset::{CharFallback, CharRom};
+use crate::hd44780::{self, GlyphId, Instr, Io, LcdCore};
+use crate::lcd_i2c::{LcdError, LcdGeometry, LcdResult, WaitMode};
+
+/// Versi async `LcdI2c`: delay per nibble di-`await`, tidak memblok executor
+pub struct LcdI2cAsync<I2C> {
+    i2c: I2C,
+    addr: u8,
+    core: LcdCore,
+}
+
+impl<I2C> LcdI2cAsync<I2C>
+where
+    I2C: I2c,
+{
+    pub async fn new<D>(i2c: I2C, addr: u8, geometry: LcdGeometry, delay: &mut D) -> LcdResult<Self, I2C>
+    where
+        D: DelayNs,
+    {
+        Self::with_backpack(i2c, addr, geometry, Backpack::default(), delay).await
+    }
+
+    /// Simpan bus tanpa I/O; `init` wajib dipanggil sebelum dipakai.
+    /// Cocok untuk LCD yang mungkin belum terpasang saat boot.
+    pub fn uninit(i2c: I2C, addr: u8, geometry: LcdGeometry, backpack: Backpack) -> Self {
+        Self {
+            i2c,
+            addr,
+            core: LcdCore::new(geometry, backpack),
+        }
+    }
+
+    /// Seperti `new`, untuk backpack dengan wiring lain
+    pub async fn with_backpack<D>(
+        i2c: I2C,
+        addr: u8,
+        geometry: LcdGeometry,
+        backpack: Backpack,
+        delay: &mut D,
+    ) -> LcdResult<Self, I2C>
+    where
+        D: DelayNs,
+    {
+        let mut lcd = Self::uninit(i2c, addr, geometry, backpack);
+        lcd.init(delay).await?;
+        Ok(lcd)
+    }
+
+    /// Init sequence (4-bit mode); aman diulang setelah LCD dicabut-pasang.
+    /// Glyph yang sudah terdaftar di-upload ulang.
+    pub async fn init<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        let plan = self.core.init();
+        self.run(plan, delay).await
+    }
+
+    pub async fn clear<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.clear(), delay).await
+    }
+
+    pub fn backpack(&self) -> Backpack {
+        self.core.backpack
+    }
+
+    pub fn geometry(&self) -> LcdGeometry {
+        self.core.geometry
+    }
+
+    pub fn cols(&self) -> u8 {
+        self.core.geometry.cols
+    }
+
+    pub fn rows(&self) -> u8 {
+        self.core.geometry.rows
+    }
+
+    pub async fn set_cursor<D: DelayNs>(&mut self, col: u8, row: u8, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.set_cursor(col, row)?, delay).await
+    }
+
+    /// Tulis teks UTF-8; karakter dipetakan ke ROM (lihat `set_rom`/`set_fallback`)
+    pub async fn print<D: DelayNs>(&mut self, text: &str, delay: &mut D) -> LcdResult<(), I2C> {
+        for c in text.chars() {
+            let code = self.encode(c, delay).await?;
+            self.write_char(code, delay).await?;
+        }
+        Ok(())
+    }
+
+    // ───────── display control ─────────
+
+    pub async fn set_backlight(&mut self, on: bool) -> LcdResult<(), I2C> {
+        let plan = self.core.set_backlight(on);
+        self.run(plan, &mut NoDelay).await
+    }
+
+    pub fn backlight(&self) -> bool {
+        self.core.backlight
+    }
+
+    /// Matikan tampilan tanpa menghapus isi DDRAM
+    pub async fn set_display_on<D: DelayNs>(&mut self, on: bool, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.set_control(hd44780::DISPLAY_ON, on), delay).await
+    }
+
+    pub fn display_on(&self) -> bool {
+        self.core.control & hd44780::DISPLAY_ON != 0
+    }
+
+    /// Kursor garis bawah di posisi address counter
+    pub async fn set_cursor_visible<D: DelayNs>(&mut self, on: bool, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.set_control(hd44780::CURSOR_ON, on), delay).await
+    }
+
+    pub fn cursor_visible(&self) -> bool {
+        self.core.control & hd44780::CURSOR_ON != 0
+    }
+
+    pub async fn set_blink<D: DelayNs>(&mut self, on: bool, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.set_control(hd44780::BLINK_ON, on), delay).await
+    }
+
+    pub fn blink(&self) -> bool {
+        self.core.control & hd44780::BLINK_ON != 0
+    }
+
+    /// Geser seluruh tampilan satu kolom ke kiri (isi DDRAM tetap)
+    pub async fn scroll_left<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.scroll(false), delay).await
+    }
+
+    pub async fn scroll_right<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.scroll(true), delay).await
+    }
+
+    /// Kursor ke (0, 0) dan batalkan semua shift
+    pub async fn home<D: DelayNs>(&mut self, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.home(), delay).await
+    }
+
+    /// Pilih mode tunggu. `BusyFlag` diabaikan bila backpack tidak bisa dibaca.
+    pub fn set_wait_mode(&mut self, wait: WaitMode) {
+        self.core.set_wait_mode(wait);
+    }
+
+    /// Mode tunggu aktif; `Timed` setelah busy flag timeout sampai `init` berikutnya
+    pub fn wait_mode(&self) -> WaitMode {
+        self.core.wait_mode()
+    }
+
+    // ───────── charset ─────────
+
+    /// ROM karakter yang terpasang di controller (tertulis di chip: ...A00 / ...A02)
+    pub fn set_rom(&mut self, rom: CharRom) {
+        self.core.rom = rom;
+    }
+
+    pub fn rom(&self) -> CharRom {
+        self.core.rom
+    }
+
+    pub fn set_fallback(&mut self, fallback: CharFallback) {
+        self.core.fallback = fallback;
+    }
+
+    pub fn fallback(&self) -> CharFallback {
+        self.core.fallback
+    }
+
+    /// Kode karakter untuk `c`: ROM, lalu glyph fallback di CGRAM
+    /// (di-upload bila perlu), lalu karakter pengganti.
+    pub async fn encode<D: DelayNs>(&mut self, c: char, delay: &mut D) -> LcdResult<u8, I2C> {
+        let (code, plan) = self.core.encode(c);
+        self.run(plan, delay).await?;
+        Ok(code)
+    }
+
+    // ───────── CGRAM ─────────
+
+    /// Upload glyph 5x8 ke CGRAM `slot` (0..8), menimpa glyph terdaftar di slot itu.
+    /// Posisi kursor tidak berubah.
+    pub async fn create_char<D: DelayNs>(&mut self, slot: u8, bitmap: &[u8; 8], delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.create_char(slot, bitmap)?, delay).await
+    }
+
+    /// Slot CGRAM untuk glyph `id`; upload ke slot kosong bila belum ada
+    pub async fn load_glyph<D: DelayNs>(&mut self, id: GlyphId, bitmap: &[u8; 8], delay: &mut D) -> LcdResult<u8, I2C> {
+        let (slot, plan) = self.core.load_glyph(id, bitmap)?;
+        self.run(plan, delay).await?;
+        Ok(slot)
+    }
+
+    pub fn glyph_slot(&self, id: GlyphId) -> Option<u8> {
+        self.core.cgram.find(id)
+    }
+
+    /// Bebaskan slot glyph; isi CGRAM tetap sampai slot dipakai lagi
+    pub fn release_glyph(&mut self, id: GlyphId) {
+        self.core.cgram.release(id);
+    }
+
+    pub fn clear_glyphs(&mut self) {
+        self.core.cgram.clear();
+    }
+
+    /// Tulis satu kode karakter di posisi kursor (0..8 = glyph CGRAM)
+    pub async fn write_char<D: DelayNs>(&mut self, code: u8, delay: &mut D) -> LcdResult<(), I2C> {
+        self.run(self.core.write_char(code), delay).await
+    }
+
+    // ───────── executor ─────────
+
+    /// Jalankan instruksi berurutan; berhenti di error I2C pertama
+    async fn run<D: DelayNs>(&mut self, plan: impl IntoIterator<Item = Instr>, delay: &mut D) -> LcdResult<(), I2C> {
+        for instr in plan {
+            let mut exec = self.core.begin(instr);
+            let mut read = None;
+            while let Some(io) = self.core.next(&mut exec, read.take()) {
+                match io {
+                    Io::Write(frame) => self.i2c.write(self.addr, frame.bytes()).await.map_err(LcdError::from_i2c)?,
+                    Io::Read => {
+                        let mut buf = [0];
+                        self.i2c.read(self.addr, &mut buf).await.map_err(LcdError::from_i2c)?;
+                        read = Some(buf[0]);
+                    }
+                    Io::DelayUs(us) => delay.delay_us(us).await,
+                    Io::DelayNs(ns) => delay.delay_ns(ns).await,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Untuk operasi tanpa delay (tulis port backlight)
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
@@ -1,25 +1,296 @@
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::{ErrorType, OutputPin, StatefulOutputPin};
+use embedded_hal::pwm::SetDutyCycle;
 
-pub struct Led<PIN>
+/// Wiring LED: menyala saat pin high (default) atau low
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Polarity {
+    #[default]
+    ActiveHigh,
+    ActiveLow,
+}
+
+/// Keluaran LED: pin digital atau kanal PWM (lewat `Pwm`)
+pub trait LedOutput {
+    type Error;
+
+    /// Kecerahan perseptual 0..=255; pin digital menyala bila > 0
+    fn set_level(&mut self, level: u8, polarity: Polarity) -> Result<(), Self::Error>;
+}
+
+impl<P: OutputPin> LedOutput for P {
+    type Error = P::Error;
+
+    fn set_level(&mut self, level: u8, polarity: Polarity) -> Result<(), Self::Error> {
+        if (level > 0) == (polarity == Polarity::ActiveHigh) {
+            self.set_high()
+        } else {
+            self.set_low()
+        }
+    }
+}
+
+/// Kanal PWM sebagai keluaran LED, dengan koreksi gamma
+pub struct Pwm<P>(pub P);
+
+impl<P: SetDutyCycle> LedOutput for Pwm<P> {
+    type Error = P::Error;
+
+    fn set_level(&mut self, level: u8, polarity: Polarity) -> Result<(), Self::Error> {
+        let duty = match polarity {
+            Polarity::ActiveHigh => gamma(level),
+            Polarity::ActiveLow => 255 - gamma(level),
+        };
+        self.0.set_duty_cycle_fraction(duty as u16, 255)
+    }
+}
+
+/// Kurva gamma ~2: kecerahan perseptual ke duty cycle (0 tetap 0, 1.. minimal 1)
+pub fn gamma(level: u8) -> u8 {
+    let level = level as u16;
+    (level * level).div_ceil(255) as u8
+}
+
+// ───────── pola ─────────
+
+/// Pola kedip yang dijalankan `Led::tick` tanpa delay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// Kedip `times` kali lalu padam
+    Blink { times: u8, on_ms: u32, off_ms: u32 },
+    /// Dua denyut pendek per detik, berulang
+    Heartbeat,
+    /// Terang-redup halus satu siklus per `period_ms`, berulang
+    Breathe { period_ms: u32 },
+    /// Teks Morse (A-Z, 0-9, spasi) berulang dengan jeda kata; `unit_ms` = panjang titik
+    Morse { text: &'static str, unit_ms: u32 },
+}
+
+impl Pattern {
+    /// Kecerahan pada `t` ms sejak pola mulai; `None` bila pola sudah selesai
+    pub fn level_at(&self, t: u64) -> Option<u8> {
+        match *self {
+            Pattern::Blink { times, on_ms, off_ms } => {
+                let period = (on_ms as u64 + off_ms as u64).max(1);
+                if t >= period * times as u64 {
+                    return None;
+                }
+                Some(if t % period < on_ms as u64 { 255 } else { 0 })
+            }
+            Pattern::Heartbeat => {
+                let phase = t % 1000;
+                Some(if phase < 100 || (200..300).contains(&phase) { 255 } else { 0 })
+            }
+            Pattern::Breathe { period_ms } => {
+                let period = (period_ms as u64).max(2);
+                let (half, phase) = (period / 2, t % period);
+                let rise = if phase < half { phase } else { period - phase };
+                Some((rise * 255 / half).min(255) as u8)
+            }
+            Pattern::Morse { text, unit_ms } => Some(morse_level(text, t / (unit_ms as u64).max(1))),
+        }
+    }
+}
+
+/// Titik/garis huruf; `None` untuk karakter tanpa kode
+fn morse_code(c: char) -> Option<&'static str> {
+    const LETTERS: [&str; 26] = [
+        ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+        "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
+    ];
+    const DIGITS: [&str; 10] = [
+        "-----", ".----", "..---", "...--", "....-", ".....", "-....", "--...", "---..", "----.",
+    ];
+
+    match c.to_ascii_uppercase() {
+        c @ 'A'..='Z' => Some(LETTERS[c as usize - 'A' as usize]),
+        c @ '0'..='9' => Some(DIGITS[c as usize - '0' as usize]),
+        _ => None,
+    }
+}
+
+/// Panggil `f(on, units)` untuk tiap elemen satu putaran teks; berhenti bila `f` false
+fn morse_walk(text: &str, mut f: impl FnMut(bool, u64) -> bool) {
+    for c in text.chars() {
+        if c == ' ' {
+            // 3 unit setelah huruf + 4 = jeda kata 7 unit
+            if !f(false, 4) {
+                return;
+            }
+            continue;
+        }
+        let Some(code) = morse_code(c) else {
+            continue;
+        };
+        for (i, element) in code.bytes().enumerate() {
+            if i > 0 && !f(false, 1) {
+                return;
+            }
+            if !f(true, if element == b'.' { 1 } else { 3 }) {
+                return;
+            }
+        }
+        if !f(false, 3) {
+            return;
+        }
+    }
+    // jeda kata sebelum teks diulang
+    f(false, 4);
+}
+
+fn morse_level(text: &str, unit: u64) -> u8 {
+    let mut total = 0;
+    morse_walk(text, |_, units| {
+        total += units;
+        true
+    });
+
+    let mut left = unit % total;
+    let mut on = false;
+    morse_walk(text, |lit, units| {
+        if left < units {
+            on = lit;
+            return false;
+        }
+        left -= units;
+        true
+    });
+
+    if on {
+        255
+    } else {
+        0
+    }
+}
+
+// ───────── LED ─────────
+
+type LedResult<OUT> = Result<(), <OUT as LedOutput>::Error>;
+
+pub struct Led<OUT>
 where
-    PIN: OutputPin,
+    OUT: LedOutput,
 {
-    pin: PIN,
+    out: OUT,
+    polarity: Polarity,
+    // level terakhir yang berhasil dikirim; None = belum pernah / tidak pasti
+    level: Option<u8>,
+    pattern: Option<Pattern>,
+    started_ms: Option<u64>,
 }
 
-impl<PIN> Led<PIN>
+impl<OUT> Led<OUT>
 where
-    PIN: OutputPin,
+    OUT: LedOutput,
 {
-    pub fn new(pin: PIN) -> Self {
-        Self { pin }
+    pub fn new(out: OUT) -> Self {
+        Self::with_polarity(out, Polarity::ActiveHigh)
     }
 
-    pub fn set(&mut self, on: bool) {
-        if on {
-            let _ =self.pin.set_high();
-        } else {
-            let _ = self.pin.set_low();
+    pub fn with_polarity(out: OUT, polarity: Polarity) -> Self {
+        Self {
+            out,
+            polarity,
+            level: None,
+            pattern: None,
+            started_ms: None,
         }
     }
+
+    pub fn polarity(&self) -> Polarity {
+        self.polarity
+    }
+
+    /// Nyala penuh/padam; menghentikan pola yang berjalan
+    pub fn set(&mut self, on: bool) -> LedResult<OUT> {
+        self.set_brightness(if on { 255 } else { 0 })
+    }
+
+    /// Kecerahan perseptual 0..=255; menghentikan pola yang berjalan
+    pub fn set_brightness(&mut self, level: u8) -> LedResult<OUT> {
+        self.pattern = None;
+        self.write(level)
+    }
+
+    /// Kecerahan terakhir yang berhasil dikirim ke keluaran
+    pub fn brightness(&self) -> u8 {
+        self.level.unwrap_or(0)
+    }
+
+    /// Menyala menurut state yang dicatat driver (tanpa membaca pin)
+    pub fn is_lit(&self) -> bool {
+        self.brightness() > 0
+    }
+
+    /// Mulai pola pada `tick` berikutnya, menggantikan pola sebelumnya
+    pub fn play(&mut self, pattern: Pattern) {
+        self.pattern = Some(pattern);
+        self.started_ms = None;
+    }
+
+    pub fn pattern(&self) -> Option<Pattern> {
+        self.pattern
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.pattern.is_some()
+    }
+
+    /// Majukan pola; keluaran hanya ditulis bila level berubah (atau
+    /// penulisan sebelumnya gagal). Pola yang selesai membuat LED padam.
+    pub fn tick(&mut self, now_ms: u64) -> LedResult<OUT> {
+        let Some(pattern) = self.pattern else {
+            return Ok(());
+        };
+        let start = *self.started_ms.get_or_insert(now_ms);
+
+        let level = match pattern.level_at(now_ms.saturating_sub(start)) {
+            Some(level) => level,
+            None => {
+                self.pattern = None;
+                0
+            }
+        };
+        if self.level == Some(level) {
+            return Ok(());
+        }
+        self.write(level)
+    }
+
+    pub fn into_inner(self) -> OUT {
+        self.out
+    }
+
+    fn write(&mut self, level: u8) -> LedResult<OUT> {
+        match self.out.set_level(level, self.polarity) {
+            Ok(()) => {
+                self.level = Some(level);
+                Ok(())
+            }
+            Err(e) => {
+                self.level = None;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<PIN> Led<PIN>
+where
+    PIN: StatefulOutputPin,
+{
+    /// Baca state pin sebenarnya, memperhitungkan polaritas
+    pub fn is_on(&mut self) -> Result<bool, <PIN as ErrorType>::Error> {
+        let high = self.out.is_set_high()?;
+        Ok(high == (self.polarity == Polarity::ActiveHigh))
+    }
+
+    /// Balik state pin; menghentikan pola yang berjalan
+    pub fn toggle(&mut self) -> Result<(), <PIN as ErrorType>::Error> {
+        self.pattern = None;
+        self.level = None;
+        self.out.toggle()?;
+        let on = self.is_on()?;
+        self.level = Some(if on { 255 } else { 0 });
+        Ok(())
+    }
 }
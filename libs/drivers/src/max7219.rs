@@ -0,0 +1,143 @@
+//! MAX7219 lewat SPI: display 8 digit 7-segment atau matriks 8x8, bisa di-daisy-chain.
+//! Device 0 adalah chip pertama di chain (paling dekat MCU).
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::segment::{self, SegmentPanel, DP};
+
+/// Batas chain yang didukung
+pub const MAX_DEVICES: usize = 8;
+
+// ───────── register ─────────
+
+const NOOP: u8 = 0x00;
+const DIGIT0: u8 = 0x01;
+const DECODE_MODE: u8 = 0x09;
+const INTENSITY: u8 = 0x0A;
+const SCAN_LIMIT: u8 = 0x0B;
+const SHUTDOWN: u8 = 0x0C;
+const DISPLAY_TEST: u8 = 0x0F;
+
+pub struct Max7219<SPI> {
+    spi: SPI,
+    devices: usize,
+}
+
+impl<SPI> Max7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// `devices` dibatasi 1..=8
+    pub fn new(spi: SPI, devices: usize) -> Self {
+        Self {
+            spi,
+            devices: devices.clamp(1, MAX_DEVICES),
+        }
+    }
+
+    /// Semua chip: tanpa BCD decode, scan 8 digit, kecerahan sedang, aktif, layar kosong
+    pub fn init(&mut self) -> Result<(), SPI::Error> {
+        self.write_all(DISPLAY_TEST, 0)?;
+        self.write_all(DECODE_MODE, 0)?;
+        self.write_all(SCAN_LIMIT, 7)?;
+        self.write_all(INTENSITY, 7)?;
+        self.clear()?;
+        self.write_all(SHUTDOWN, 1)
+    }
+
+    pub fn devices(&self) -> usize {
+        self.devices
+    }
+
+    /// Kecerahan 0..=15 untuk semua chip
+    pub fn set_intensity(&mut self, level: u8) -> Result<(), SPI::Error> {
+        self.write_all(INTENSITY, level.min(15))
+    }
+
+    pub fn set_power(&mut self, on: bool) -> Result<(), SPI::Error> {
+        self.write_all(SHUTDOWN, on as u8)
+    }
+
+    pub fn clear(&mut self) -> Result<(), SPI::Error> {
+        for digit in 0..8 {
+            self.write_all(DIGIT0 + digit, 0)?;
+        }
+        Ok(())
+    }
+
+    /// Satu register digit/baris (0..8) di satu chip
+    pub fn write_digit(&mut self, device: usize, digit: u8, value: u8) -> Result<(), SPI::Error> {
+        if device >= self.devices || digit >= 8 {
+            return Ok(());
+        }
+
+        let mut frame = [NOOP; MAX_DEVICES * 2];
+        let frame = &mut frame[..self.devices * 2];
+        // byte pertama terdorong paling jauh di chain
+        let slot = (self.devices - 1 - device) * 2;
+        frame[slot] = DIGIT0 + digit;
+        frame[slot + 1] = value;
+        self.spi.write(frame)
+    }
+
+    /// Matriks 8x8: `rows[0]` ke register DIGIT0
+    pub fn write_matrix(&mut self, device: usize, rows: &[u8; 8]) -> Result<(), SPI::Error> {
+        for (digit, &row) in rows.iter().enumerate() {
+            self.write_digit(device, digit as u8, row)?;
+        }
+        Ok(())
+    }
+
+    /// Teks 7-segment rata kiri di seluruh chain
+    pub fn write_str(&mut self, text: &str) -> Result<(), SPI::Error> {
+        let mut segments = [0; MAX_DEVICES * 8];
+        let len = self.devices * 8;
+        segment::encode_str(text, &mut segments[..len]);
+        self.write_segments_raw(&segments[..len])
+    }
+
+    /// Digit paling kiri = DIGIT7 chip terakhir; digit paling kanan = DIGIT0 chip 0
+    fn write_segments_raw(&mut self, segments: &[u8]) -> Result<(), SPI::Error> {
+        let len = self.devices * 8;
+        for (i, &seg) in segments.iter().take(len).enumerate() {
+            let from_right = len - 1 - i;
+            self.write_digit(from_right / 8, (from_right % 8) as u8, to_register(seg))?;
+        }
+        Ok(())
+    }
+
+    // ───────── low-level ─────────
+
+    /// Register sama ke semua chip dalam satu transaksi
+    fn write_all(&mut self, register: u8, value: u8) -> Result<(), SPI::Error> {
+        let mut frame = [0; MAX_DEVICES * 2];
+        let frame = &mut frame[..self.devices * 2];
+        for pair in frame.chunks_exact_mut(2) {
+            pair.copy_from_slice(&[register, value]);
+        }
+        self.spi.write(frame)
+    }
+}
+
+/// Urutan bit MAX7219 tanpa decode: DP A B C D E F G (bit 7..0)
+fn to_register(seg: u8) -> u8 {
+    let mut out = seg & DP;
+    for bit in 0..7 {
+        if seg & (1 << bit) != 0 {
+            out |= 1 << (6 - bit);
+        }
+    }
+    out
+}
+
+impl<SPI: SpiDevice> SegmentPanel for Max7219<SPI> {
+    type Error = SPI::Error;
+
+    fn digits(&self) -> u8 {
+        (self.devices * 8) as u8
+    }
+
+    fn write_segments(&mut self, segments: &[u8]) -> Result<(), Self::Error> {
+        self.write_segments_raw(segments)
+    }
+}
@@ -0,0 +1,76 @@
+//! Font 7-segment bersama untuk TM1637 dan MAX7219.
+//! Pola: bit 0..6 = segmen a..g, bit 7 = titik desimal (urutan TM1637).
+
+/// Titik desimal
+pub const DP: u8 = 0x80;
+
+/// Panel satu baris digit 7-segment
+pub trait SegmentPanel {
+    type Error;
+
+    fn digits(&self) -> u8;
+
+    /// Tulis pola segmen mulai digit paling kiri
+    fn write_segments(&mut self, segments: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Pola segmen untuk `c`; karakter tanpa bentuk yang masuk akal jadi kosong
+pub fn encode(c: char) -> u8 {
+    match c {
+        '0' | 'O' => 0x3F,
+        '1' => 0x06,
+        '2' => 0x5B,
+        '3' => 0x4F,
+        '4' => 0x66,
+        '5' | 'S' | 's' => 0x6D,
+        '6' => 0x7D,
+        '7' => 0x07,
+        '8' => 0x7F,
+        '9' => 0x6F,
+        'A' | 'a' => 0x77,
+        'B' | 'b' => 0x7C,
+        'C' => 0x39,
+        'c' => 0x58,
+        'D' | 'd' => 0x5E,
+        'E' | 'e' => 0x79,
+        'F' | 'f' => 0x71,
+        'G' | 'g' => 0x3D,
+        'H' => 0x76,
+        'h' => 0x74,
+        'I' | 'i' => 0x30,
+        'J' | 'j' => 0x1E,
+        'L' | 'l' => 0x38,
+        'N' | 'n' => 0x54,
+        'o' => 0x5C,
+        'P' | 'p' => 0x73,
+        'Q' | 'q' => 0x67,
+        'R' | 'r' => 0x50,
+        'T' | 't' => 0x78,
+        'U' => 0x3E,
+        'u' => 0x1C,
+        'Y' | 'y' => 0x6E,
+        '-' => 0x40,
+        '_' => 0x08,
+        '=' => 0x48,
+        '°' => 0x63,
+        _ => 0x00,
+    }
+}
+
+/// Encode teks ke `out`; '.' menyalakan DP digit sebelumnya, bukan memakai digit baru.
+/// Mengembalikan jumlah digit terisi.
+pub fn encode_str(text: &str, out: &mut [u8]) -> usize {
+    let mut n = 0;
+    for c in text.chars() {
+        if c == '.' && n > 0 && out[n - 1] & DP == 0 {
+            out[n - 1] |= DP;
+            continue;
+        }
+        if n == out.len() {
+            break;
+        }
+        out[n] = if c == '.' { DP } else { encode(c) };
+        n += 1;
+    }
+    n
+}
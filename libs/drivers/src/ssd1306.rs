@@ -0,0 +1,261 @@
+//! OLED 128x64 / 128x32 SSD1306 dan SH1106 lewat I2C.
+//! Semua gambar masuk ke framebuffer; `flush()` hanya mengirim kolom yang berubah per page.
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, SevenBitAddress};
+use heapless::Vec;
+
+/// Error driver OLED, generik atas error bus I2C
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OledError<E> {
+    /// Modul tidak menjawab (dicabut / alamat salah)
+    Nack(E),
+    /// Error bus lain
+    Bus(E),
+}
+
+impl<E: embedded_hal::i2c::Error> OledError<E> {
+    fn from_i2c(err: E) -> Self {
+        match err.kind() {
+            ErrorKind::NoAcknowledge(_) => OledError::Nack(err),
+            _ => OledError::Bus(err),
+        }
+    }
+}
+
+impl<E: embedded_hal::i2c::Error> embedded_hal::i2c::Error for OledError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            OledError::Nack(e) | OledError::Bus(e) => e.kind(),
+        }
+    }
+}
+
+pub(crate) type OledResult<T, I2C> = Result<T, OledError<<I2C as ErrorType>::Error>>;
+
+/// Chip controller panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OledController {
+    Ssd1306,
+    /// RAM 132 kolom, panel 128 kolom mulai kolom 2; tanpa horizontal addressing
+    Sh1106,
+}
+
+impl OledController {
+    fn column_offset(self) -> u8 {
+        match self {
+            OledController::Ssd1306 => 0,
+            OledController::Sh1106 => 2,
+        }
+    }
+}
+
+pub const WIDTH: usize = 128;
+pub const MAX_HEIGHT: usize = 64;
+const PAGES: usize = MAX_HEIGHT / 8;
+
+// byte kontrol I2C: sisa transaksi berisi perintah / data GDDRAM
+const CONTROL_COMMAND: u8 = 0x00;
+const CONTROL_DATA: u8 = 0x40;
+
+// ───────── instruction set ─────────
+
+const DISPLAY_OFF: u8 = 0xAE;
+const DISPLAY_ON: u8 = 0xAF;
+const SET_CONTRAST: u8 = 0x81;
+const NORMAL: u8 = 0xA6;
+const INVERTED: u8 = 0xA7;
+const SET_PAGE: u8 = 0xB0;
+const COLUMN_LOW: u8 = 0x00;
+const COLUMN_HIGH: u8 = 0x10;
+
+fn init_commands(controller: OledController, height: u8) -> Vec<u8, 32> {
+    let mut cmds = Vec::new();
+    let _ = cmds.extend_from_slice(&[DISPLAY_OFF, 0xD5, 0x80, 0xA8, height - 1, 0xD3, 0x00, 0x40]);
+    let _ = match controller {
+        // charge pump on, page addressing
+        OledController::Ssd1306 => cmds.extend_from_slice(&[0x8D, 0x14, 0x20, 0x02]),
+        // DC-DC on
+        OledController::Sh1106 => cmds.extend_from_slice(&[0xAD, 0x8B]),
+    };
+    let com_pins = if height > 32 { 0x12 } else { 0x02 };
+    let _ = cmds.extend_from_slice(&[
+        0xA1, 0xC8, 0xDA, com_pins, SET_CONTRAST, 0xCF, 0xD9, 0xF1, 0xDB, 0x40, 0xA4, NORMAL, DISPLAY_ON,
+    ]);
+    cmds
+}
+
+pub struct Oled<I2C> {
+    i2c: I2C,
+    addr: SevenBitAddress,
+    controller: OledController,
+    height: u8,
+    // page x kolom, bit 0 = baris teratas page
+    buffer: [[u8; WIDTH]; PAGES],
+    // rentang kolom (awal, akhir) yang berubah sejak flush terakhir
+    dirty: [Option<(u8, u8)>; PAGES],
+}
+
+impl<I2C> Oled<I2C>
+where
+    I2C: I2c,
+{
+    /// Belum ada transaksi I2C; panggil `init` sebelum dipakai.
+    /// `height` 32 atau 64 (dibulatkan ke kelipatan 8).
+    pub fn new(i2c: I2C, addr: SevenBitAddress, controller: OledController, height: u8) -> Self {
+        let height = (height.clamp(8, MAX_HEIGHT as u8) / 8) * 8;
+
+        Self {
+            i2c,
+            addr,
+            controller,
+            height,
+            buffer: [[0; WIDTH]; PAGES],
+            dirty: [None; PAGES],
+        }
+    }
+
+    /// Init controller lalu kirim seluruh framebuffer; aman diulang (hot-plug)
+    pub fn init(&mut self) -> OledResult<(), I2C> {
+        self.commands(&init_commands(self.controller, self.height))?;
+        self.mark_all_dirty();
+        self.flush()
+    }
+
+    pub fn width(&self) -> u8 {
+        WIDTH as u8
+    }
+
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    pub fn controller(&self) -> OledController {
+        self.controller
+    }
+
+    // ───────── framebuffer ─────────
+
+    pub fn clear(&mut self) {
+        for page in 0..self.pages() {
+            if self.buffer[page].iter().any(|&b| b != 0) {
+                self.buffer[page] = [0; WIDTH];
+                self.dirty[page] = Some((0, WIDTH as u8 - 1));
+            }
+        }
+    }
+
+    /// Pixel di luar panel diabaikan
+    pub fn set_pixel(&mut self, x: usize, y: usize, on: bool) {
+        if x >= WIDTH || y >= self.height as usize {
+            return;
+        }
+
+        let (page, bit) = (y / 8, 1 << (y % 8));
+        let cell = &mut self.buffer[page][x];
+        let old = *cell;
+        if on {
+            *cell |= bit;
+        } else {
+            *cell &= !bit;
+        }
+
+        if *cell != old {
+            let x = x as u8;
+            self.dirty[page] = Some(match self.dirty[page] {
+                Some((lo, hi)) => (lo.min(x), hi.max(x)),
+                None => (x, x),
+            });
+        }
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        x < WIDTH && y < self.height as usize && self.buffer[y / 8][x] & (1 << (y % 8)) != 0
+    }
+
+    /// Kirim kolom yang berubah, satu transaksi data per page
+    pub fn flush(&mut self) -> OledResult<(), I2C> {
+        for page in 0..self.pages() {
+            let Some((lo, hi)) = self.dirty[page] else {
+                continue;
+            };
+
+            let col = lo + self.controller.column_offset();
+            self.commands(&[SET_PAGE | page as u8, COLUMN_LOW | (col & 0x0F), COLUMN_HIGH | (col >> 4)])?;
+
+            let mut data: Vec<u8, { WIDTH + 1 }> = Vec::new();
+            let _ = data.push(CONTROL_DATA);
+            let _ = data.extend_from_slice(&self.buffer[page][lo as usize..=hi as usize]);
+            self.i2c.write(self.addr, &data).map_err(OledError::from_i2c)?;
+
+            self.dirty[page] = None;
+        }
+        Ok(())
+    }
+
+    // ───────── display control ─────────
+
+    pub fn set_contrast(&mut self, level: u8) -> OledResult<(), I2C> {
+        self.commands(&[SET_CONTRAST, level])
+    }
+
+    /// Mode sleep panel; isi GDDRAM tetap
+    pub fn set_display_on(&mut self, on: bool) -> OledResult<(), I2C> {
+        self.commands(&[if on { DISPLAY_ON } else { DISPLAY_OFF }])
+    }
+
+    pub fn set_inverted(&mut self, inverted: bool) -> OledResult<(), I2C> {
+        self.commands(&[if inverted { INVERTED } else { NORMAL }])
+    }
+
+    // ───────── low-level ─────────
+
+    fn pages(&self) -> usize {
+        self.height as usize / 8
+    }
+
+    fn mark_all_dirty(&mut self) {
+        self.dirty = [Some((0, WIDTH as u8 - 1)); PAGES];
+    }
+
+    fn commands(&mut self, cmds: &[u8]) -> OledResult<(), I2C> {
+        let mut bytes: Vec<u8, 33> = Vec::new();
+        let _ = bytes.push(CONTROL_COMMAND);
+        let _ = bytes.extend_from_slice(cmds);
+        self.i2c.write(self.addr, &bytes).map_err(OledError::from_i2c)
+    }
+}
+
+// ───────── embedded-graphics ─────────
+
+#[cfg(feature = "graphics")]
+mod graphics {
+    use embedded_graphics::pixelcolor::BinaryColor;
+    use embedded_graphics::prelude::{DrawTarget, OriginDimensions, Pixel, Size};
+    use embedded_hal::i2c::I2c;
+
+    use super::{Oled, WIDTH};
+
+    impl<I2C: I2c> OriginDimensions for Oled<I2C> {
+        fn size(&self) -> Size {
+            Size::new(WIDTH as u32, self.height as u32)
+        }
+    }
+
+    /// Menggambar hanya mengubah framebuffer; panggil `flush()` untuk menampilkan
+    impl<I2C: I2c> DrawTarget for Oled<I2C> {
+        type Color = BinaryColor;
+        type Error = core::convert::Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            for Pixel(point, color) in pixels {
+                if let (Ok(x), Ok(y)) = (usize::try_from(point.x), usize::try_from(point.y)) {
+                    self.set_pixel(x, y, color.is_on());
+                }
+            }
+            Ok(())
+        }
+    }
+}
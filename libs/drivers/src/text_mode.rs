@@ -0,0 +1,153 @@
+//! Mode teks di atas target embedded-graphics: grid sel 6x8 pixel
+//! (21x8 pada OLED 128x64, 40x30 pada TFT 240x240)
+
+use embedded_graphics::mono_font::ascii::FONT_5X8;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::prelude::{OriginDimensions, Point, Primitive, Size};
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Baseline, Text};
+use embedded_graphics::Drawable;
+use embedded_hal::i2c::I2c;
+
+use crate::ssd1306::{Oled, OledResult};
+
+const CELL_WIDTH: u32 = 6;
+const CELL_HEIGHT: u32 = 8;
+
+/// Grid maksimum (TFT 320x240 = 53x40 sel)
+const MAX_COLS: usize = 64;
+const MAX_ROWS: usize = 40;
+
+/// Panel grafis yang bisa menjadi layar teks
+pub trait TextTarget: embedded_graphics::draw_target::DrawTarget + OriginDimensions {
+    type FlushError;
+
+    const FOREGROUND: Self::Color;
+    const BACKGROUND: Self::Color;
+
+    /// Tampilkan yang sudah digambar; no-op untuk panel yang menggambar langsung
+    fn flush(&mut self) -> Result<(), Self::FlushError>;
+}
+
+impl<I2C: I2c> TextTarget for Oled<I2C> {
+    type FlushError = crate::ssd1306::OledError<I2C::Error>;
+
+    const FOREGROUND: Self::Color = embedded_graphics::pixelcolor::BinaryColor::On;
+    const BACKGROUND: Self::Color = embedded_graphics::pixelcolor::BinaryColor::Off;
+
+    fn flush(&mut self) -> OledResult<(), I2C> {
+        Oled::flush(self)
+    }
+}
+
+pub struct TextMode<T: TextTarget> {
+    target: T,
+    fg: T::Color,
+    bg: T::Color,
+    // isi tiap sel, untuk menggambar ulang baris yang gagal
+    cells: [[u8; MAX_COLS]; MAX_ROWS],
+    // bit per baris: gambar terakhir gagal, `redraw` mengulangnya
+    dirty: u64,
+}
+
+/// Mode teks OLED
+pub type OledText<I2C> = TextMode<Oled<I2C>>;
+
+impl<T: TextTarget> TextMode<T> {
+    pub fn new(target: T) -> Self {
+        Self::with_colors(target, T::FOREGROUND, T::BACKGROUND)
+    }
+
+    pub fn with_colors(target: T, fg: T::Color, bg: T::Color) -> Self {
+        Self {
+            target,
+            fg,
+            bg,
+            cells: [[b' '; MAX_COLS]; MAX_ROWS],
+            dirty: 0,
+        }
+    }
+
+    pub fn cols(&self) -> u8 {
+        (self.target.size().width / CELL_WIDTH).min(MAX_COLS as u32) as u8
+    }
+
+    pub fn rows(&self) -> u8 {
+        (self.target.size().height / CELL_HEIGHT).min(MAX_ROWS as u32) as u8
+    }
+
+    /// Tulis teks mulai sel (col, row), dipotong di tepi kanan.
+    /// Karakter di luar ASCII tampil sebagai '?'. Bila gagal, baris
+    /// ditandai dan digambar ulang oleh `redraw`.
+    pub fn write_at(&mut self, col: u8, row: u8, text: &str) -> Result<(), T::Error> {
+        if row >= self.rows() {
+            return Ok(());
+        }
+
+        let start = col as usize;
+        let width = self.cols().saturating_sub(col) as usize;
+        let line = &mut self.cells[row as usize];
+        let mut end = start;
+        for (cell, c) in line[start..].iter_mut().zip(text.chars().take(width)) {
+            *cell = if c.is_ascii() { c as u8 } else { b'?' };
+            end += 1;
+        }
+
+        let result = (start..end).try_for_each(|col| self.draw_cell(col, row as usize));
+        if result.is_err() {
+            self.dirty |= 1 << row;
+        }
+        result
+    }
+
+    /// Kosongkan layar; bila gagal semua baris digambar ulang oleh `redraw`
+    pub fn clear(&mut self) -> Result<(), T::Error> {
+        self.cells = [[b' '; MAX_COLS]; MAX_ROWS];
+        self.dirty = 0;
+
+        let result = self.target.clear(self.bg);
+        if result.is_err() {
+            self.dirty = (1 << self.rows()) - 1;
+        }
+        result
+    }
+
+    /// Gambar ulang baris yang sebelumnya gagal
+    pub fn redraw(&mut self) -> Result<(), T::Error> {
+        for row in 0..self.rows() as usize {
+            if self.dirty & (1 << row) == 0 {
+                continue;
+            }
+            (0..self.cols() as usize).try_for_each(|col| self.draw_cell(col, row))?;
+            self.dirty &= !(1 << row);
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), T::FlushError> {
+        self.target.flush()
+    }
+
+    /// Akses ke panel untuk grafis campuran
+    pub fn target_mut(&mut self) -> &mut T {
+        &mut self.target
+    }
+
+    pub fn into_inner(self) -> T {
+        self.target
+    }
+
+    fn draw_cell(&mut self, col: usize, row: usize) -> Result<(), T::Error> {
+        let origin = Point::new((col as u32 * CELL_WIDTH) as i32, (row as u32 * CELL_HEIGHT) as i32);
+        let cell = Size::new(CELL_WIDTH, CELL_HEIGHT);
+        Rectangle::new(origin, cell)
+            .into_styled(PrimitiveStyle::with_fill(self.bg))
+            .draw(&mut self.target)?;
+
+        let mut buf = [0; 4];
+        let c = self.cells[row][col] as char;
+        let style = MonoTextStyle::new(&FONT_5X8, self.fg);
+        Text::with_baseline(c.encode_utf8(&mut buf), origin, style, Baseline::Top).draw(&mut self.target)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,425 @@
+//! TFT warna lewat SPI: ST7735, ST7789, ILI9341 (RGB565, pin D/C terpisah)
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::SpiDevice;
+
+/// Error driver TFT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TftError<SE, PE> {
+    Spi(SE),
+    /// Pin D/C gagal di-drive
+    Pin(PE),
+    /// Window di luar layar
+    OutOfBounds,
+}
+
+pub(crate) type TftResult<T, SPI, DC> =
+    Result<T, TftError<<SPI as embedded_hal::spi::ErrorType>::Error, <DC as embedded_hal::digital::ErrorType>::Error>>;
+
+/// Chip controller
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TftModel {
+    St7735,
+    St7789,
+    Ili9341,
+}
+
+impl TftModel {
+    /// MADCTL untuk rotasi 0/90/180/270 (tanpa bit BGR)
+    fn madctl(self, rotation: Rotation) -> u8 {
+        let table = match self {
+            TftModel::St7735 => [MY | MX, MY | MV, 0, MX | MV],
+            TftModel::St7789 => [0, MX | MV, MX | MY, MY | MV],
+            TftModel::Ili9341 => [MX, MV, MY, MX | MY | MV],
+        };
+        table[rotation as usize]
+    }
+}
+
+/// Rotasi layar searah jarum jam
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    Deg0 = 0,
+    Deg90 = 1,
+    Deg180 = 2,
+    Deg270 = 3,
+}
+
+impl Rotation {
+    fn swaps_axes(self) -> bool {
+        matches!(self, Rotation::Deg90 | Rotation::Deg270)
+    }
+}
+
+/// Model dan ukuran panel (orientasi portrait / rotasi 0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TftConfig {
+    pub model: TftModel,
+    pub width: u16,
+    pub height: u16,
+    /// Offset RAM controller ke pixel (0, 0) panel, tanpa mirror (MX/MY = 0)
+    pub col_offset: u16,
+    pub row_offset: u16,
+    /// Ukuran RAM controller (portrait); offset rotasi yang di-mirror dihitung darinya
+    pub ram_width: u16,
+    pub ram_height: u16,
+    /// Urutan subpixel panel BGR
+    pub bgr: bool,
+    /// Panel IPS umumnya butuh inversi warna
+    pub invert: bool,
+}
+
+impl TftConfig {
+    pub const ST7735_128X160: Self = Self {
+        model: TftModel::St7735,
+        width: 128,
+        height: 160,
+        col_offset: 0,
+        row_offset: 0,
+        ram_width: 128,
+        ram_height: 160,
+        bgr: false,
+        invert: false,
+    };
+
+    pub const ST7789_240X240: Self = Self {
+        model: TftModel::St7789,
+        width: 240,
+        height: 240,
+        col_offset: 0,
+        row_offset: 0,
+        // panel 240x240 di atas RAM 240x320
+        ram_width: 240,
+        ram_height: 320,
+        bgr: false,
+        invert: true,
+    };
+
+    pub const ST7789_240X320: Self = Self {
+        height: 320,
+        ..Self::ST7789_240X240
+    };
+
+    pub const ILI9341_240X320: Self = Self {
+        model: TftModel::Ili9341,
+        width: 240,
+        height: 320,
+        col_offset: 0,
+        row_offset: 0,
+        ram_width: 240,
+        ram_height: 320,
+        bgr: true,
+        invert: false,
+    };
+}
+
+// ───────── instruction set ─────────
+
+const SWRESET: u8 = 0x01;
+const SLPOUT: u8 = 0x11;
+const NORON: u8 = 0x13;
+const INVOFF: u8 = 0x20;
+const INVON: u8 = 0x21;
+const DISPOFF: u8 = 0x28;
+const DISPON: u8 = 0x29;
+const CASET: u8 = 0x2A;
+const RASET: u8 = 0x2B;
+const RAMWR: u8 = 0x2C;
+const MADCTL: u8 = 0x36;
+const COLMOD: u8 = 0x3A;
+
+const MY: u8 = 0x80;
+const MX: u8 = 0x40;
+const MV: u8 = 0x20;
+const BGR: u8 = 0x08;
+
+/// 16 bit per pixel
+const COLMOD_RGB565: u8 = 0x55;
+
+/// Pixel per transaksi SPI saat fill
+const CHUNK_PIXELS: usize = 64;
+
+pub struct Tft<SPI, DC> {
+    spi: SPI,
+    dc: DC,
+    config: TftConfig,
+    rotation: Rotation,
+}
+
+impl<SPI, DC> Tft<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    /// Belum ada transaksi SPI; panggil `init` sebelum dipakai
+    pub fn new(spi: SPI, dc: DC, config: TftConfig) -> Self {
+        Self {
+            spi,
+            dc,
+            config,
+            rotation: Rotation::Deg0,
+        }
+    }
+
+    /// Software reset lalu konfigurasi minimal: RGB565, rotasi, inversi, display on
+    pub fn init<D: DelayNs>(&mut self, delay: &mut D) -> TftResult<(), SPI, DC> {
+        self.command(SWRESET, &[])?;
+        delay.delay_ms(150);
+        self.command(SLPOUT, &[])?;
+        delay.delay_ms(120);
+
+        self.command(COLMOD, &[COLMOD_RGB565])?;
+        self.command(MADCTL, &[self.madctl()])?;
+        self.command(if self.config.invert { INVON } else { INVOFF }, &[])?;
+        self.command(NORON, &[])?;
+        self.command(DISPON, &[])?;
+        delay.delay_ms(20);
+        Ok(())
+    }
+
+    pub fn config(&self) -> TftConfig {
+        self.config
+    }
+
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Lebar setelah rotasi
+    pub fn width(&self) -> u16 {
+        if self.rotation.swaps_axes() {
+            self.config.height
+        } else {
+            self.config.width
+        }
+    }
+
+    pub fn height(&self) -> u16 {
+        if self.rotation.swaps_axes() {
+            self.config.width
+        } else {
+            self.config.height
+        }
+    }
+
+    pub fn set_rotation(&mut self, rotation: Rotation) -> TftResult<(), SPI, DC> {
+        self.rotation = rotation;
+        self.command(MADCTL, &[self.madctl()])
+    }
+
+    pub fn set_display_on(&mut self, on: bool) -> TftResult<(), SPI, DC> {
+        self.command(if on { DISPON } else { DISPOFF }, &[])
+    }
+
+    // ───────── gambar ─────────
+
+    /// Isi persegi dengan satu warna RGB565; dipotong di tepi layar
+    pub fn fill_rect(&mut self, x: u16, y: u16, w: u16, h: u16, color: u16) -> TftResult<(), SPI, DC> {
+        let w = w.min(self.width().saturating_sub(x));
+        let h = h.min(self.height().saturating_sub(y));
+        if w == 0 || h == 0 {
+            return Ok(());
+        }
+
+        self.set_window(x, y, w, h)?;
+
+        let mut chunk = [0; CHUNK_PIXELS * 2];
+        for px in chunk.chunks_exact_mut(2) {
+            px.copy_from_slice(&color.to_be_bytes());
+        }
+
+        let mut left = w as usize * h as usize;
+        while left > 0 {
+            let n = left.min(CHUNK_PIXELS);
+            self.data(&chunk[..n * 2])?;
+            left -= n;
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self, color: u16) -> TftResult<(), SPI, DC> {
+        self.fill_rect(0, 0, self.width(), self.height(), color)
+    }
+
+    /// Tulis `pixels` (baris demi baris, RGB565) ke window (x, y, w, h)
+    pub fn blit(&mut self, x: u16, y: u16, w: u16, h: u16, pixels: &[u16]) -> TftResult<(), SPI, DC> {
+        self.write_pixels(x, y, w, h, pixels.iter().copied())
+    }
+
+    /// Seperti `blit`, dari iterator; berhenti bila iterator habis
+    pub fn write_pixels<I>(&mut self, x: u16, y: u16, w: u16, h: u16, pixels: I) -> TftResult<(), SPI, DC>
+    where
+        I: IntoIterator<Item = u16>,
+    {
+        // dibandingkan dalam u32 agar x + w tidak overflow untuk koordinat besar
+        let fits = |pos: u16, len: u16, max: u16| len > 0 && pos as u32 + len as u32 <= max as u32;
+        if !fits(x, w, self.width()) || !fits(y, h, self.height()) {
+            return Err(TftError::OutOfBounds);
+        }
+
+        self.set_window(x, y, w, h)?;
+
+        let mut chunk = [0; CHUNK_PIXELS * 2];
+        let mut pixels = pixels.into_iter().take(w as usize * h as usize);
+        loop {
+            let mut n = 0;
+            for (slot, px) in chunk.chunks_exact_mut(2).zip(&mut pixels) {
+                slot.copy_from_slice(&px.to_be_bytes());
+                n += 1;
+            }
+            if n == 0 {
+                return Ok(());
+            }
+            self.data(&chunk[..n * 2])?;
+        }
+    }
+
+    // ───────── low-level ─────────
+
+    fn madctl(&self) -> u8 {
+        let bgr = if self.config.bgr { BGR } else { 0 };
+        self.config.model.madctl(self.rotation) | bgr
+    }
+
+    /// Offset (x, y) untuk rotasi aktif. MX/MY membalik alamat kolom/baris,
+    /// sehingga panel yang lebih kecil dari RAM bergeser ke sisi seberang.
+    fn offsets(&self) -> (u16, u16) {
+        let config = &self.config;
+        let madctl = config.model.madctl(self.rotation);
+
+        let col = if madctl & MX != 0 {
+            config.ram_width.saturating_sub(config.width + config.col_offset)
+        } else {
+            config.col_offset
+        };
+        let row = if madctl & MY != 0 {
+            config.ram_height.saturating_sub(config.height + config.row_offset)
+        } else {
+            config.row_offset
+        };
+
+        if madctl & MV != 0 {
+            (row, col)
+        } else {
+            (col, row)
+        }
+    }
+
+    /// CASET/RASET lalu RAMWR; data pixel berikutnya mengisi window ini
+    fn set_window(&mut self, x: u16, y: u16, w: u16, h: u16) -> TftResult<(), SPI, DC> {
+        let (col_offset, row_offset) = self.offsets();
+
+        let (x0, x1) = (x + col_offset, x + w - 1 + col_offset);
+        let (y0, y1) = (y + row_offset, y + h - 1 + row_offset);
+
+        let [x0h, x0l] = x0.to_be_bytes();
+        let [x1h, x1l] = x1.to_be_bytes();
+        self.command(CASET, &[x0h, x0l, x1h, x1l])?;
+
+        let [y0h, y0l] = y0.to_be_bytes();
+        let [y1h, y1l] = y1.to_be_bytes();
+        self.command(RASET, &[y0h, y0l, y1h, y1l])?;
+
+        self.command(RAMWR, &[])
+    }
+
+    fn command(&mut self, cmd: u8, args: &[u8]) -> TftResult<(), SPI, DC> {
+        self.dc.set_low().map_err(TftError::Pin)?;
+        self.spi.write(&[cmd]).map_err(TftError::Spi)?;
+        if !args.is_empty() {
+            self.data(args)?;
+        }
+        Ok(())
+    }
+
+    fn data(&mut self, bytes: &[u8]) -> TftResult<(), SPI, DC> {
+        self.dc.set_high().map_err(TftError::Pin)?;
+        self.spi.write(bytes).map_err(TftError::Spi)
+    }
+}
+
+// ───────── embedded-graphics ─────────
+
+#[cfg(feature = "graphics")]
+mod graphics {
+    use embedded_graphics::pixelcolor::raw::{RawData, RawU16};
+    use embedded_graphics::pixelcolor::{Rgb565, RgbColor};
+    use embedded_graphics::prelude::{Dimensions, DrawTarget, OriginDimensions, Pixel, PointsIter, Size};
+    use embedded_graphics::primitives::Rectangle;
+    use embedded_hal::digital::OutputPin;
+    use embedded_hal::spi::SpiDevice;
+
+    use super::{Tft, TftError};
+    use crate::text_mode::TextTarget;
+
+    fn raw(color: Rgb565) -> u16 {
+        RawU16::from(color).into_inner()
+    }
+
+    impl<SPI: SpiDevice, DC: OutputPin> OriginDimensions for Tft<SPI, DC> {
+        fn size(&self) -> Size {
+            Size::new(self.width() as u32, self.height() as u32)
+        }
+    }
+
+    /// Menggambar langsung ke panel (tanpa framebuffer)
+    impl<SPI: SpiDevice, DC: OutputPin> DrawTarget for Tft<SPI, DC> {
+        type Color = Rgb565;
+        type Error = TftError<SPI::Error, DC::Error>;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            let (w, h) = (self.width() as i32, self.height() as i32);
+            for Pixel(p, color) in pixels {
+                if (0..w).contains(&p.x) && (0..h).contains(&p.y) {
+                    self.blit(p.x as u16, p.y as u16, 1, 1, &[raw(color)])?;
+                }
+            }
+            Ok(())
+        }
+
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            if area.size.width == 0 || area.size.height == 0 {
+                return Ok(());
+            }
+
+            let visible = area.intersection(&self.bounding_box());
+            if visible == *area {
+                let (x, y) = (area.top_left.x as u16, area.top_left.y as u16);
+                let (w, h) = (area.size.width as u16, area.size.height as u16);
+                return self.write_pixels(x, y, w, h, colors.into_iter().map(raw));
+            }
+
+            // sebagian di luar layar: per pixel, hanya yang terlihat
+            let pixels = area.points().zip(colors).map(|(p, c)| Pixel(p, c));
+            self.draw_iter(pixels)
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+            let area = area.intersection(&self.bounding_box());
+            if area.size.width == 0 || area.size.height == 0 {
+                return Ok(());
+            }
+            let (x, y) = (area.top_left.x as u16, area.top_left.y as u16);
+            self.fill_rect(x, y, area.size.width as u16, area.size.height as u16, raw(color))
+        }
+    }
+
+    impl<SPI: SpiDevice, DC: OutputPin> TextTarget for Tft<SPI, DC> {
+        type FlushError = TftError<SPI::Error, DC::Error>;
+
+        const FOREGROUND: Rgb565 = Rgb565::WHITE;
+        const BACKGROUND: Rgb565 = Rgb565::BLACK;
+
+        fn flush(&mut self) -> Result<(), Self::FlushError> {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! TM1637 (modul 4/6 digit) lewat dua GPIO bit-bang.
+//! Protokol mirip I2C tanpa alamat, LSB dulu; DIO harus open-drain dengan pull-up.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+use crate::segment::{self, SegmentPanel};
+
+/// Error driver TM1637
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tm1637Error<E> {
+    Pin(E),
+    /// Chip tidak menarik DIO low pada clock ACK
+    Nack,
+}
+
+type Tm1637Result<T, E> = Result<T, Tm1637Error<E>>;
+
+pub const MAX_DIGITS: u8 = 6;
+
+// ───────── instruction set ─────────
+
+const DATA_AUTO_INCREMENT: u8 = 0x40;
+const ADDRESS: u8 = 0xC0;
+const DISPLAY_CONTROL: u8 = 0x80;
+const DISPLAY_ON: u8 = 0x08;
+
+/// Setengah periode clock (~100 kHz)
+const BIT_DELAY_US: u32 = 5;
+
+pub struct Tm1637<CLK, DIO, D> {
+    clk: CLK,
+    dio: DIO,
+    delay: D,
+    digits: u8,
+    brightness: u8,
+    on: bool,
+}
+
+impl<CLK, DIO, D, E> Tm1637<CLK, DIO, D>
+where
+    CLK: OutputPin + ErrorType<Error = E>,
+    DIO: OutputPin + InputPin + ErrorType<Error = E>,
+    D: DelayNs,
+{
+    /// Belum ada komunikasi; panggil `init` untuk menyalakan display
+    pub fn new(clk: CLK, dio: DIO, delay: D, digits: u8) -> Self {
+        Self {
+            clk,
+            dio,
+            delay,
+            digits: digits.min(MAX_DIGITS),
+            brightness: 7,
+            on: true,
+        }
+    }
+
+    /// Kosongkan semua digit lalu kirim display control
+    pub fn init(&mut self) -> Tm1637Result<(), E> {
+        self.clear()?;
+        self.display_control()
+    }
+
+    /// Kecerahan 0..=7
+    pub fn set_brightness(&mut self, level: u8) -> Tm1637Result<(), E> {
+        self.brightness = level.min(7);
+        self.display_control()
+    }
+
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    pub fn set_display_on(&mut self, on: bool) -> Tm1637Result<(), E> {
+        self.on = on;
+        self.display_control()
+    }
+
+    pub fn clear(&mut self) -> Tm1637Result<(), E> {
+        self.write_raw(0, &[0; MAX_DIGITS as usize][..self.digits as usize])
+    }
+
+    /// Tulis teks rata kiri; '.' menyalakan titik digit sebelumnya
+    pub fn write_str(&mut self, text: &str) -> Tm1637Result<(), E> {
+        let mut segments = [0; MAX_DIGITS as usize];
+        segment::encode_str(text, &mut segments[..self.digits as usize]);
+        self.write_raw(0, &segments[..self.digits as usize])
+    }
+
+    /// Tulis pola segmen mulai digit `pos`
+    pub fn write_raw(&mut self, pos: u8, segments: &[u8]) -> Tm1637Result<(), E> {
+        let len = segments.len().min(self.digits.saturating_sub(pos) as usize);
+
+        self.start()?;
+        self.write_byte(DATA_AUTO_INCREMENT)?;
+        self.stop()?;
+
+        self.start()?;
+        self.write_byte(ADDRESS | pos)?;
+        for &seg in &segments[..len] {
+            self.write_byte(seg)?;
+        }
+        self.stop()
+    }
+
+    // ───────── low-level ─────────
+
+    fn display_control(&mut self) -> Tm1637Result<(), E> {
+        let on = if self.on { DISPLAY_ON } else { 0 };
+        self.start()?;
+        self.write_byte(DISPLAY_CONTROL | on | self.brightness)?;
+        self.stop()
+    }
+
+    /// DIO turun saat CLK high; bus dinaikkan dulu bila frame sebelumnya terputus
+    fn start(&mut self) -> Tm1637Result<(), E> {
+        self.dio.set_high().map_err(Tm1637Error::Pin)?;
+        self.clk.set_high().map_err(Tm1637Error::Pin)?;
+        self.delay.delay_us(BIT_DELAY_US);
+        self.dio.set_low().map_err(Tm1637Error::Pin)?;
+        self.delay.delay_us(BIT_DELAY_US);
+        Ok(())
+    }
+
+    /// DIO naik saat CLK high
+    fn stop(&mut self) -> Tm1637Result<(), E> {
+        self.clk.set_low().map_err(Tm1637Error::Pin)?;
+        self.dio.set_low().map_err(Tm1637Error::Pin)?;
+        self.delay.delay_us(BIT_DELAY_US);
+        self.clk.set_high().map_err(Tm1637Error::Pin)?;
+        self.delay.delay_us(BIT_DELAY_US);
+        self.dio.set_high().map_err(Tm1637Error::Pin)?;
+        self.delay.delay_us(BIT_DELAY_US);
+        Ok(())
+    }
+
+    /// 8 bit LSB dulu, lalu clock ke-9 untuk ACK
+    fn write_byte(&mut self, byte: u8) -> Tm1637Result<(), E> {
+        for bit in 0..8 {
+            self.clk.set_low().map_err(Tm1637Error::Pin)?;
+            if byte & (1 << bit) != 0 {
+                self.dio.set_high().map_err(Tm1637Error::Pin)?;
+            } else {
+                self.dio.set_low().map_err(Tm1637Error::Pin)?;
+            }
+            self.delay.delay_us(BIT_DELAY_US);
+            self.clk.set_high().map_err(Tm1637Error::Pin)?;
+            self.delay.delay_us(BIT_DELAY_US);
+        }
+
+        // lepas DIO, chip menariknya low sebagai ACK
+        self.clk.set_low().map_err(Tm1637Error::Pin)?;
+        self.dio.set_high().map_err(Tm1637Error::Pin)?;
+        self.delay.delay_us(BIT_DELAY_US);
+        self.clk.set_high().map_err(Tm1637Error::Pin)?;
+        let ack = self.dio.is_low().map_err(Tm1637Error::Pin)?;
+        self.delay.delay_us(BIT_DELAY_US);
+        self.clk.set_low().map_err(Tm1637Error::Pin)?;
+
+        if ack {
+            Ok(())
+        } else {
+            Err(Tm1637Error::Nack)
+        }
+    }
+}
+
+impl<CLK, DIO, D, E> SegmentPanel for Tm1637<CLK, DIO, D>
+where
+    CLK: OutputPin + ErrorType<Error = E>,
+    DIO: OutputPin + InputPin + ErrorType<Error = E>,
+    D: DelayNs,
+{
+    type Error = Tm1637Error<E>;
+
+    fn digits(&self) -> u8 {
+        self.digits
+    }
+
+    fn write_segments(&mut self, segments: &[u8]) -> Result<(), Self::Error> {
+        self.write_raw(0, segments)
+    }
+}
@@ -1,20 +1,29 @@
+#[cfg(target_os = "espidf")]
 use esp_idf_svc::wifi::{EspWifi, ClientConfiguration, Configuration};
+#[cfg(target_os = "espidf")]
 use esp_idf_svc::eventloop::EspSystemEventLoop;
+#[cfg(target_os = "espidf")]
 use esp_idf_svc::nvs::EspDefaultNvsPartition;
+#[cfg(target_os = "espidf")]
 use esp_idf_hal::modem::Modem;
+#[cfg(target_os = "espidf")]
 use esp_idf_svc::sys::EspError;
+#[cfg(target_os = "espidf")]
 use heapless::String as HString;
 
-pub struct WifiDriver {
-    wifi: EspWifi<'static>,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WifiStatus {
     Connected,
     Disconnected,
 }
 
+/// Driver WiFi ESP-IDF (hanya tersedia di target espidf)
+#[cfg(target_os = "espidf")]
+pub struct WifiDriver {
+    wifi: EspWifi<'static>,
+}
+
+#[cfg(target_os = "espidf")]
 impl WifiDriver {
     pub fn new(
         modem: Modem,
@@ -0,0 +1,293 @@
+//! LED addressable WS2812/SK6812: buffer warna, encoder bit ke simbol
+//! SPI atau RMT, dan efek yang dijalankan `tick` tanpa delay.
+
+use embedded_hal::spi::SpiBus;
+
+use crate::color::{Effect, Rgb};
+
+/// Urutan byte per LED di kabel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PixelFormat {
+    /// WS2812B / SK6812 RGB
+    #[default]
+    Grb,
+    /// SK6812 RGBW: putih diambil dari komponen bersama r/g/b
+    Grbw,
+}
+
+impl PixelFormat {
+    /// Byte kabel untuk satu warna; `len` = 3 atau 4
+    pub fn encode(self, c: Rgb) -> ([u8; 4], usize) {
+        match self {
+            PixelFormat::Grb => ([c.g, c.r, c.b, 0], 3),
+            PixelFormat::Grbw => {
+                let w = c.r.min(c.g).min(c.b);
+                ([c.g - w, c.r - w, c.b - w, w], 4)
+            }
+        }
+    }
+}
+
+/// Timing bit dalam ns dan jeda reset (latch) dalam µs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub t0h_ns: u32,
+    pub t0l_ns: u32,
+    pub t1h_ns: u32,
+    pub t1l_ns: u32,
+    pub reset_us: u32,
+}
+
+impl Timing {
+    pub const WS2812: Timing = Timing {
+        t0h_ns: 400,
+        t0l_ns: 850,
+        t1h_ns: 800,
+        t1l_ns: 450,
+        reset_us: 50,
+    };
+
+    pub const SK6812: Timing = Timing {
+        t0h_ns: 300,
+        t0l_ns: 900,
+        t1h_ns: 600,
+        t1l_ns: 600,
+        reset_us: 80,
+    };
+
+    /// Simbol RMT untuk bit 0 dan 1 pada clock `tick_hz`
+    pub fn symbols(&self, tick_hz: u32) -> (RmtSymbol, RmtSymbol) {
+        let ticks = |ns: u32| ((ns as u64 * tick_hz as u64 + 500_000_000) / 1_000_000_000).max(1) as u16;
+        (
+            RmtSymbol { high_ticks: ticks(self.t0h_ns), low_ticks: ticks(self.t0l_ns) },
+            RmtSymbol { high_ticks: ticks(self.t1h_ns), low_ticks: ticks(self.t1l_ns) },
+        )
+    }
+}
+
+/// Satu bit sebagai pulsa high lalu low, dalam tick RMT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RmtSymbol {
+    pub high_ticks: u16,
+    pub low_ticks: u16,
+}
+
+/// Simbol RMT untuk deretan byte, MSB dulu
+pub fn rmt_symbols(
+    bytes: impl Iterator<Item = u8>,
+    zero: RmtSymbol,
+    one: RmtSymbol,
+) -> impl Iterator<Item = RmtSymbol> {
+    bytes.flat_map(move |byte| (0..8).rev().map(move |bit| if byte & (1 << bit) != 0 { one } else { zero }))
+}
+
+/// Encoding SPI @3.2 MHz: tiap bit data jadi 4 bit SPI (0 = 1000, 1 = 1110),
+/// jadi satu byte data = 4 byte SPI
+pub fn spi_symbols(byte: u8) -> [u8; 4] {
+    let nibble = |bit: u8| if byte & (1 << bit) != 0 { 0b1110 } else { 0b1000 };
+    let mut out = [0; 4];
+    for (i, b) in out.iter_mut().enumerate() {
+        let hi = 7 - 2 * i as u8;
+        *b = (nibble(hi) << 4) | nibble(hi - 1);
+    }
+    out
+}
+
+/// Pengirim frame ke strip: terima byte kabel (sudah urut GRB) lalu kirim jeda reset
+pub trait SymbolWriter {
+    type Error;
+
+    fn write_frame(&mut self, bytes: impl Iterator<Item = u8>) -> Result<(), Self::Error>;
+}
+
+/// Strip lewat MOSI SPI pada 3.2 MHz (pin lain tidak dipakai)
+pub struct SpiWriter<SPI> {
+    spi: SPI,
+}
+
+/// Nol setelah frame: 40 byte @3.2 MHz = 100 µs, cukup untuk WS2812 dan SK6812
+const SPI_RESET_BYTES: usize = 40;
+
+impl<SPI: SpiBus> SpiWriter<SPI> {
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    pub fn into_inner(self) -> SPI {
+        self.spi
+    }
+}
+
+impl<SPI: SpiBus> SymbolWriter for SpiWriter<SPI> {
+    type Error = SPI::Error;
+
+    fn write_frame(&mut self, bytes: impl Iterator<Item = u8>) -> Result<(), Self::Error> {
+        let mut chunk = [0; 64];
+        let mut len = 0;
+        for byte in bytes {
+            chunk[len..len + 4].copy_from_slice(&spi_symbols(byte));
+            len += 4;
+            if len == chunk.len() {
+                self.spi.write(&chunk)?;
+                len = 0;
+            }
+        }
+        self.spi.write(&chunk[..len])?;
+        self.spi.write(&[0; SPI_RESET_BYTES])?;
+        self.spi.flush()
+    }
+}
+
+#[cfg(target_os = "espidf")]
+pub use rmt::RmtWriter;
+
+#[cfg(target_os = "espidf")]
+mod rmt {
+    use esp_idf_hal::delay::Ets;
+    use esp_idf_hal::rmt::{PinState, Pulse, PulseTicks, TxRmtDriver, VariableLengthSignal};
+    use esp_idf_svc::sys::EspError;
+
+    use super::{rmt_symbols, RmtSymbol, SymbolWriter, Timing};
+
+    /// Strip lewat peripheral RMT (mis. GPIO8 di C3-DevKitM)
+    pub struct RmtWriter<'d> {
+        tx: TxRmtDriver<'d>,
+        zero: RmtSymbol,
+        one: RmtSymbol,
+        reset_us: u32,
+    }
+
+    impl<'d> RmtWriter<'d> {
+        pub fn new(tx: TxRmtDriver<'d>, timing: Timing) -> Result<Self, EspError> {
+            let tick_hz = tx.counter_clock()?.0;
+            let (zero, one) = timing.symbols(tick_hz);
+            Ok(Self { tx, zero, one, reset_us: timing.reset_us })
+        }
+    }
+
+    impl SymbolWriter for RmtWriter<'_> {
+        type Error = EspError;
+
+        fn write_frame(&mut self, bytes: impl Iterator<Item = u8>) -> Result<(), Self::Error> {
+            let mut signal = VariableLengthSignal::new();
+            for symbol in rmt_symbols(bytes, self.zero, self.one) {
+                let high = Pulse::new(PinState::High, PulseTicks::new(symbol.high_ticks)?);
+                let low = Pulse::new(PinState::Low, PulseTicks::new(symbol.low_ticks)?);
+                signal.push([&high, &low])?;
+            }
+            self.tx.start_blocking(&signal)?;
+            Ets::delay_us(self.reset_us);
+            Ok(())
+        }
+    }
+}
+
+// ───────── strip ─────────
+
+/// Strip `N` LED. Warna disimpan apa adanya; kecerahan dan gamma
+/// diterapkan saat `show`.
+pub struct Ws2812<W, const N: usize> {
+    writer: W,
+    format: PixelFormat,
+    pixels: [Rgb; N],
+    brightness: u8,
+    gamma: bool,
+    effect: Option<Effect>,
+    started_ms: Option<u64>,
+}
+
+impl<W: SymbolWriter, const N: usize> Ws2812<W, N> {
+    pub fn new(writer: W, format: PixelFormat) -> Self {
+        Self {
+            writer,
+            format,
+            pixels: [Rgb::BLACK; N],
+            brightness: 255,
+            gamma: true,
+            effect: None,
+            started_ms: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Kecerahan global 0..=255 (LED WS2812 sangat terang; 32..64 biasanya cukup)
+    pub fn set_brightness(&mut self, level: u8) {
+        self.brightness = level;
+    }
+
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    pub fn set_gamma(&mut self, enabled: bool) {
+        self.gamma = enabled;
+    }
+
+    /// Warna satu LED; indeks di luar strip diabaikan. Menghentikan efek.
+    pub fn set(&mut self, index: usize, color: impl Into<Rgb>) {
+        self.effect = None;
+        if let Some(px) = self.pixels.get_mut(index) {
+            *px = color.into();
+        }
+    }
+
+    pub fn fill(&mut self, color: impl Into<Rgb>) {
+        self.effect = None;
+        self.pixels.fill(color.into());
+    }
+
+    pub fn clear(&mut self) {
+        self.fill(Rgb::BLACK);
+    }
+
+    pub fn pixels(&self) -> &[Rgb; N] {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [Rgb; N] {
+        self.effect = None;
+        &mut self.pixels
+    }
+
+    /// Mulai efek pada `tick` berikutnya
+    pub fn play(&mut self, effect: Effect) {
+        self.effect = Some(effect);
+        self.started_ms = None;
+    }
+
+    pub fn effect(&self) -> Option<Effect> {
+        self.effect
+    }
+
+    /// Render frame efek pada `now_ms` lalu kirim; tanpa efek tidak melakukan apa-apa
+    pub fn tick(&mut self, now_ms: u64) -> Result<(), W::Error> {
+        let Some(effect) = self.effect else {
+            return Ok(());
+        };
+        let start = *self.started_ms.get_or_insert(now_ms);
+        effect.render(now_ms.saturating_sub(start), &mut self.pixels);
+        self.show()
+    }
+
+    /// Kirim buffer ke strip
+    pub fn show(&mut self) -> Result<(), W::Error> {
+        let (format, brightness, gamma) = (self.format, self.brightness, self.gamma);
+        let bytes = self.pixels.iter().flat_map(move |&c| {
+            let c = c.scale(brightness);
+            let (wire, len) = format.encode(if gamma { c.gamma() } else { c });
+            wire.into_iter().take(len)
+        });
+        self.writer.write_frame(bytes)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
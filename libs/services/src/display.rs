@@ -1,12 +1,31 @@
-use drivers::LcdI2c;
+use drivers::charset;
+use drivers::{GlyphId, LcdError, LcdI2c};
 use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::{I2c, SevenBitAddress};
 
-/// Service level abstraction (APP LOGIC)
-pub struct LcdDisplay<I2C, D> {
+use crate::framebuffer::{FrameBuffer, MAX_COLS, MAX_ROWS};
+use crate::icons::Icon;
+use crate::layout::{self, Align, Marquee};
+use crate::text_display::TextDisplay;
+
+/// Service level abstraction (APP LOGIC).
+/// Semua tulisan masuk ke framebuffer; `flush()` mengirim sel yang berubah saja.
+pub struct LcdDisplay<I2C, D>
+where
+    I2C: I2c<SevenBitAddress>,
+{
     lcd: Option<LcdI2c<I2C>>,
     delay: D,
+    fb: FrameBuffer,
+    marquees: [Option<Marquee>; MAX_ROWS],
+    // LCD belum di-init, tidak menjawab atau keluar sinkron; init ulang sebelum operasi berikutnya
     error: bool,
+    last_error: Option<LcdError<I2C::Error>>,
+    // screen saver: backlight mati setelah `saver_ms` tanpa perubahan isi
+    saver_ms: Option<u64>,
+    last_active_ms: u64,
+    active: bool,
+    asleep: bool,
 }
 
 impl<I2C, D> LcdDisplay<I2C, D>
@@ -15,49 +34,387 @@ where
     D: DelayNs,
 {
     pub fn new(lcd: Option<LcdI2c<I2C>>, delay: D) -> Self {
-        let error = lcd.is_none();
-        Self { lcd, delay, error }
+        let fb = match lcd.as_ref() {
+            Some(lcd) => FrameBuffer::new(lcd.cols(), lcd.rows()),
+            None => FrameBuffer::new(0, 0),
+        };
+
+        Self {
+            lcd,
+            delay,
+            fb,
+            marquees: Default::default(),
+            error: false,
+            last_error: None,
+            saver_ms: None,
+            last_active_ms: 0,
+            active: true,
+            asleep: false,
+        }
+    }
+
+    /// LCD dari `LcdI2c::uninit`: init dicoba pada operasi pertama dan
+    /// diulang seperti setelah NACK, sehingga LCD boleh dipasang belakangan
+    pub fn deferred(lcd: LcdI2c<I2C>, delay: D) -> Self {
+        let mut display = Self::new(Some(lcd), delay);
+        display.error = true;
+        display
     }
 
+    /// Kosongkan panel dan framebuffer
     pub fn init(&mut self) {
-        if let Some(lcd) = self.lcd.as_mut() {
-            if lcd.clear(&mut self.delay).is_err() {
-                self.lcd = None;
-                self.error = true;
-            }
+        self.fb.clear();
+        if self.run(|lcd, delay, _| lcd.clear(delay)).is_some() {
+            self.fb.panel_cleared();
         }
     }
 
+    /// Error terakhir dari driver (tidak dihapus oleh operasi yang berhasil)
+    pub fn last_error(&self) -> Option<&LcdError<I2C::Error>> {
+        self.last_error.as_ref()
+    }
+
+    /// LCD terpasang dan menjawab pada operasi terakhir
+    pub fn is_online(&self) -> bool {
+        self.lcd.is_some() && !self.error
+    }
+
     pub fn boot_screen(&mut self) {
-        if let Some(lcd) = self.lcd.as_mut() {
-            let _ = lcd.clear(&mut self.delay);
-            let _ = lcd.set_cursor(0, 0, &mut self.delay);
-            let _ = lcd.print("ESP32-C3 Mini", &mut self.delay);
+        self.fb.clear();
+        self.fb.put(0, 0, b"ESP32-C3 Mini");
+        self.fb.put(0, 1, b"System Ready");
+    }
 
-            let _ = lcd.set_cursor(0, 1, &mut self.delay);
-            let _ = lcd.print("System Ready", &mut self.delay);
+    pub fn show_led(&mut self, on: bool) {
+        self.fb.put(0, 1, if on { b"LED: ON " } else { b"LED: OFF" });
+    }
+
+    pub fn show_message(&mut self, line: u8, msg: &str) {
+        self.stop_marquee(line);
+        self.put_text(0, line, msg.chars());
+    }
+
+    // ───────── layout ─────────
+
+    /// Isi satu baris penuh: teks diratakan dan sisa kolom dikosongkan
+    pub fn show_aligned(&mut self, row: u8, text: &str, align: Align) {
+        self.stop_marquee(row);
+        let width = self.cols() as usize;
+        self.put_text(0, row, layout::align(text, width, align));
+    }
+
+    /// Word-wrap mulai baris `row`; mengembalikan jumlah baris terpakai
+    pub fn show_wrapped(&mut self, row: u8, text: &str) -> u8 {
+        let mut used = 0;
+        for line in layout::wrap(text, self.cols() as usize) {
+            if row + used >= self.rows() {
+                break;
+            }
+            self.show_aligned(row + used, line, Align::Left);
+            used += 1;
         }
+        used
     }
 
-    pub fn show_led(&mut self, on: bool) {
+    /// Teks yang lebih panjang dari baris berjalan lewat `tick`;
+    /// teks pendek ditampilkan biasa (rata kiri).
+    pub fn show_marquee(&mut self, row: u8, text: &str, step_ms: u64) {
+        let width = self.cols() as usize;
+        if text.chars().count() <= width {
+            self.show_aligned(row, text, Align::Left);
+            return;
+        }
+
+        let marquee = Marquee::new(text, step_ms);
+        self.put_text(0, row, marquee.window(width));
+        if let Some(slot) = self.marquees.get_mut(row as usize) {
+            *slot = Some(marquee);
+        }
+    }
+
+    pub fn stop_marquee(&mut self, row: u8) {
+        if let Some(slot) = self.marquees.get_mut(row as usize) {
+            *slot = None;
+        }
+    }
+
+    /// Majukan semua marquee dan screen saver; `true` bila framebuffer berubah (perlu flush)
+    pub fn tick(&mut self, now_ms: u64) -> bool {
+        let width = self.fb.cols() as usize;
+        let mut changed = false;
+
+        for row in 0..MAX_ROWS {
+            let Some(marquee) = self.marquees[row].as_mut() else {
+                continue;
+            };
+            if !marquee.tick(now_ms) {
+                continue;
+            }
+
+            let window: heapless::Vec<char, MAX_COLS> = marquee.window(width).collect();
+            self.put_text(0, row as u8, window.into_iter());
+            changed = true;
+        }
+
+        self.tick_saver(now_ms);
+        changed
+    }
+
+    // ───────── screen saver ─────────
+
+    /// Matikan backlight setelah `idle_ms` tanpa perubahan isi; `None` = selalu nyala.
+    /// Marquee yang berjalan terhitung perubahan isi.
+    pub fn set_screen_saver(&mut self, idle_ms: Option<u64>) {
+        self.saver_ms = idle_ms;
+        if idle_ms.is_none() {
+            self.wake();
+        }
+    }
+
+    /// Nyalakan backlight dan mulai ulang hitungan idle (mis. tombol ditekan)
+    pub fn wake(&mut self) {
+        self.active = true;
+        if self.asleep && self.run(|lcd, _, _| lcd.set_backlight(true)).is_some() {
+            self.asleep = false;
+        }
+    }
+
+    pub fn is_asleep(&self) -> bool {
+        self.asleep
+    }
+
+    fn tick_saver(&mut self, now_ms: u64) {
+        if self.active {
+            self.active = false;
+            self.last_active_ms = now_ms;
+            return;
+        }
+
+        let Some(idle_ms) = self.saver_ms else {
+            return;
+        };
+        if !self.asleep
+            && now_ms.saturating_sub(self.last_active_ms) >= idle_ms
+            && self.run(|lcd, _, _| lcd.set_backlight(false)).is_some()
+        {
+            self.asleep = true;
+        }
+    }
+
+    /// Jumlah kolom panel (0 bila LCD tidak ada)
+    pub fn cols(&self) -> u8 {
+        self.fb.cols()
+    }
+
+    pub fn rows(&self) -> u8 {
+        self.fb.rows()
+    }
+
+    pub fn clear_row(&mut self, row: u8) {
+        self.stop_marquee(row);
+        self.fb.clear_row(row);
+    }
+
+    /// Isi framebuffer satu baris (belum tentu sudah tampil)
+    pub fn row_content(&self, row: u8) -> &[u8] {
+        self.fb.row(row)
+    }
+
+    /// Kirim sel yang berbeda dari isi panel; kursor hanya dipindah
+    /// bila sel berikutnya tidak tepat di posisi address counter.
+    pub fn flush(&mut self) {
+        if !self.ready() {
+            return;
+        }
+
+        let mut sent = false;
+        self.run(|lcd, delay, fb| {
+            for row in 0..fb.rows() {
+                for col in 0..fb.cols() {
+                    let Some(byte) = fb.pending(col, row) else {
+                        continue;
+                    };
+
+                    if fb.needs_move(col, row) {
+                        lcd.set_cursor(col, row, delay)?;
+                    }
+                    fb.forget_cursor();
+                    lcd.write_char(byte, delay)?;
+                    fb.written(col, row, byte);
+                    sent = true;
+                }
+            }
+            Ok(())
+        });
+
+        if sent {
+            self.wake();
+        }
+    }
+
+    // ───────── icons ─────────
+
+    /// Slot CGRAM milik icon; upload dulu bila belum terdaftar.
+    /// `None` bila LCD tidak ada atau ke-8 slot sudah terpakai.
+    pub fn register_icon(&mut self, icon: &Icon) -> Option<u8> {
+        self.run(|lcd, delay, _| lcd.load_glyph(GlyphId::Named(icon.name), &icon.bitmap, delay))
+    }
+
+    pub fn show_icon(&mut self, col: u8, row: u8, icon: &Icon) -> bool {
+        if col >= self.cols() || row >= self.rows() {
+            return false;
+        }
+
+        match self.register_icon(icon) {
+            Some(slot) => {
+                self.fb.put(col, row, &[slot]);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Bebaskan slot CGRAM satu icon; sel yang masih memakainya berubah bila slot ditimpa
+    pub fn release_icon(&mut self, icon: &Icon) {
         if let Some(lcd) = self.lcd.as_mut() {
-            let _ = lcd.set_cursor(0, 1, &mut self.delay);
-            let _ = lcd.print(
-            if on { "LED: ON " } else { "LED: OFF" },
-            &mut self.delay);
+            lcd.release_glyph(GlyphId::Named(icon.name));
         }
     }
 
-    pub fn show_message(&mut self, line: u8, msg: &str) {
+    /// Slot icon masih dipakai sel di framebuffer
+    pub(crate) fn icon_in_use(&self, icon: &Icon) -> bool {
+        let Some(slot) = self.lcd.as_ref().and_then(|lcd| lcd.glyph_slot(GlyphId::Named(icon.name))) else {
+            return false;
+        };
+        (0..self.rows()).any(|row| self.fb.row(row).contains(&slot))
+    }
+
+    /// Lupakan semua glyph (icon dan fallback karakter); slot CGRAM boleh ditimpa lagi
+    pub fn clear_icons(&mut self) {
         if let Some(lcd) = self.lcd.as_mut() {
-            let _ = lcd.set_cursor(0, line, &mut self.delay);
-            let _ = lcd.print(msg, &mut self.delay);
+            lcd.clear_glyphs();
+        }
+    }
+
+    /// Tulis kode sel mentah (ROM/CGRAM) ke framebuffer; dipakai widget
+    pub(crate) fn put_cells(&mut self, col: u8, row: u8, cells: &[u8]) {
+        self.stop_marquee(row);
+        self.fb.put(col, row, cells);
+    }
+
+    // ───────── charset ─────────
+
+    /// Petakan teks ke kode ROM/CGRAM lalu tulis ke framebuffer mulai `col`
+    fn put_text(&mut self, col: u8, row: u8, text: impl Iterator<Item = char>) {
+        let width = self.cols().saturating_sub(col) as usize;
+        let mut cells: heapless::Vec<u8, MAX_COLS> = heapless::Vec::new();
+        for c in text.take(width) {
+            let code = self.encode(c);
+            let _ = cells.push(code);
+        }
+        self.fb.put(col, row, &cells);
+    }
+
+    /// Glyph fallback di-upload saat teks ditulis, bukan saat flush
+    fn encode(&mut self, c: char) -> u8 {
+        let Some(lcd) = self.lcd.as_ref() else {
+            return b'?';
+        };
+        if let Some(code) = charset::rom_code(lcd.rom(), c) {
+            return code;
+        }
+
+        let replacement = lcd.fallback().replacement;
+        self.run(|lcd, delay, _| lcd.encode(c, delay)).unwrap_or(replacement)
+    }
+
+    // ───────── error handling ─────────
+
+    /// Jalankan operasi LCD. Setelah error bus controller di-init ulang
+    /// (nibble bisa sudah terkirim separuh) lalu operasi dicoba sekali lagi;
+    /// NACK atau bus yang tetap error membuat panggilan berikutnya init ulang.
+    fn run<R, F>(&mut self, mut op: F) -> Option<R>
+    where
+        F: FnMut(&mut LcdI2c<I2C>, &mut D, &mut FrameBuffer) -> Result<R, LcdError<I2C::Error>>,
+    {
+        if !self.ready() {
+            return None;
+        }
+
+        let lcd = self.lcd.as_mut()?;
+        let result = match op(lcd, &mut self.delay, &mut self.fb) {
+            // recover mengosongkan panel: framebuffer menggambar ulang semua
+            Err(LcdError::Bus(_)) if self.recover() => {
+                let lcd = self.lcd.as_mut()?;
+                op(lcd, &mut self.delay, &mut self.fb)
+            }
+            other => other,
+        };
+
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.error = matches!(e, LcdError::Nack(_) | LcdError::Bus(_));
+                self.last_error = Some(e);
+                self.fb.forget_cursor();
+                None
+            }
         }
     }
 
-    pub fn clear_row(&mut self, row: u8, cols: usize) {
-        let blank = " ".repeat(cols);
-        self.show_message(row, &blank);
+    fn ready(&mut self) -> bool {
+        !self.error || self.recover()
+    }
+
+    /// Init ulang LCD yang baru dipasang kembali (driver meng-upload ulang glyph CGRAM)
+    fn recover(&mut self) -> bool {
+        let Some(lcd) = self.lcd.as_mut() else {
+            return false;
+        };
+
+        match lcd.init(&mut self.delay) {
+            Ok(()) => {
+                // panel kosong lagi; flush berikutnya menggambar ulang semua
+                self.error = false;
+                self.fb.panel_cleared();
+                true
+            }
+            Err(e) => {
+                self.last_error = Some(e);
+                false
+            }
+        }
     }
+}
 
+impl<I2C, D> TextDisplay for LcdDisplay<I2C, D>
+where
+    I2C: I2c<SevenBitAddress>,
+    D: DelayNs,
+{
+    fn cols(&self) -> u8 {
+        LcdDisplay::cols(self)
+    }
+
+    fn rows(&self) -> u8 {
+        LcdDisplay::rows(self)
+    }
+
+    fn write_at(&mut self, col: u8, row: u8, text: &str) {
+        self.stop_marquee(row);
+        self.put_text(col, row, text.chars());
+    }
+
+    fn clear(&mut self) {
+        self.marquees = Default::default();
+        self.fb.clear();
+    }
+
+    fn flush(&mut self) {
+        LcdDisplay::flush(self);
+    }
+
+    fn write_line(&mut self, row: u8, text: &str, align: Align) {
+        self.show_aligned(row, text, align);
+    }
 }
@@ -0,0 +1,175 @@
+use drivers::charset;
+use drivers::{LcdError, LcdI2cAsync};
+use embedded_hal::i2c::ErrorType;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+use crate::framebuffer::{FrameBuffer, MAX_COLS};
+
+/// Versi async `LcdDisplay`: tulisan masuk framebuffer, `flush().await`
+/// mengirim sel yang berubah tanpa memblok task lain.
+pub struct LcdDisplayAsync<I2C, D>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    lcd: Option<LcdI2cAsync<I2C>>,
+    delay: D,
+    fb: FrameBuffer,
+    // LCD belum di-init, tidak menjawab atau keluar sinkron; init ulang sebelum flush berikutnya
+    error: bool,
+    last_error: Option<LcdError<<I2C as ErrorType>::Error>>,
+}
+
+impl<I2C, D> LcdDisplayAsync<I2C, D>
+where
+    I2C: I2c<SevenBitAddress>,
+    D: DelayNs,
+{
+    pub fn new(lcd: Option<LcdI2cAsync<I2C>>, delay: D) -> Self {
+        let fb = match lcd.as_ref() {
+            Some(lcd) => FrameBuffer::new(lcd.cols(), lcd.rows()),
+            None => FrameBuffer::new(0, 0),
+        };
+
+        Self {
+            lcd,
+            delay,
+            fb,
+            error: false,
+            last_error: None,
+        }
+    }
+
+    /// LCD dari `LcdI2cAsync::uninit`: init dicoba pada operasi pertama dan
+    /// diulang seperti setelah NACK, sehingga LCD boleh dipasang belakangan
+    pub fn deferred(lcd: LcdI2cAsync<I2C>, delay: D) -> Self {
+        let mut display = Self::new(Some(lcd), delay);
+        display.error = true;
+        display
+    }
+
+    /// Kosongkan panel dan framebuffer
+    pub async fn init(&mut self) {
+        self.fb.clear();
+        if self.error && !self.recover().await {
+            return;
+        }
+        let Some(lcd) = self.lcd.as_mut() else {
+            return;
+        };
+
+        match lcd.clear(&mut self.delay).await {
+            Ok(()) => self.fb.panel_cleared(),
+            Err(e) => self.fail(e),
+        }
+    }
+
+    /// Error terakhir dari driver (tidak dihapus oleh operasi yang berhasil)
+    pub fn last_error(&self) -> Option<&LcdError<<I2C as ErrorType>::Error>> {
+        self.last_error.as_ref()
+    }
+
+    /// LCD terpasang dan menjawab pada operasi terakhir
+    pub fn is_online(&self) -> bool {
+        self.lcd.is_some() && !self.error
+    }
+
+    pub fn cols(&self) -> u8 {
+        self.fb.cols()
+    }
+
+    pub fn rows(&self) -> u8 {
+        self.fb.rows()
+    }
+
+    /// Karakter di luar ROM tampil sebagai karakter pengganti (tanpa glyph CGRAM)
+    pub fn show_message(&mut self, line: u8, msg: &str) {
+        let Some(lcd) = self.lcd.as_ref() else {
+            return;
+        };
+
+        let (rom, replacement) = (lcd.rom(), lcd.fallback().replacement);
+        let mut cells: heapless::Vec<u8, MAX_COLS> = heapless::Vec::new();
+        for c in msg.chars().take(self.fb.cols() as usize) {
+            let _ = cells.push(charset::rom_code(rom, c).unwrap_or(replacement));
+        }
+        self.fb.put(0, line, &cells);
+    }
+
+    pub fn clear_row(&mut self, row: u8) {
+        self.fb.clear_row(row);
+    }
+
+    /// Isi framebuffer satu baris (belum tentu sudah tampil)
+    pub fn row_content(&self, row: u8) -> &[u8] {
+        self.fb.row(row)
+    }
+
+    /// Kirim sel yang berbeda dari isi panel. Setelah error bus controller
+    /// di-init ulang lalu flush dicoba sekali lagi; NACK atau bus yang
+    /// tetap error membuat flush berikutnya init ulang.
+    pub async fn flush(&mut self) {
+        if self.error && !self.recover().await {
+            return;
+        }
+
+        let result = match self.flush_once().await {
+            // nibble bisa sudah terkirim separuh: sinkronkan ulang controller dulu
+            Err(LcdError::Bus(_)) if self.recover().await => self.flush_once().await,
+            other => other,
+        };
+
+        if let Err(e) = result {
+            self.fail(e);
+        }
+    }
+
+    // ───────── internal ─────────
+
+    async fn flush_once(&mut self) -> Result<(), LcdError<<I2C as ErrorType>::Error>> {
+        let Some(lcd) = self.lcd.as_mut() else {
+            return Ok(());
+        };
+
+        for row in 0..self.fb.rows() {
+            for col in 0..self.fb.cols() {
+                let Some(byte) = self.fb.pending(col, row) else {
+                    continue;
+                };
+
+                if self.fb.needs_move(col, row) {
+                    lcd.set_cursor(col, row, &mut self.delay).await?;
+                }
+                self.fb.forget_cursor();
+                lcd.write_char(byte, &mut self.delay).await?;
+                self.fb.written(col, row, byte);
+            }
+        }
+        Ok(())
+    }
+
+    async fn recover(&mut self) -> bool {
+        let Some(lcd) = self.lcd.as_mut() else {
+            return false;
+        };
+
+        match lcd.init(&mut self.delay).await {
+            Ok(()) => {
+                // panel kosong lagi; flush menggambar ulang semua
+                self.error = false;
+                self.fb.panel_cleared();
+                true
+            }
+            Err(e) => {
+                self.last_error = Some(e);
+                false
+            }
+        }
+    }
+
+    fn fail(&mut self, e: LcdError<<I2C as ErrorType>::Error>) {
+        self.error = matches!(e, LcdError::Nack(_) | LcdError::Bus(_));
+        self.last_error = Some(e);
+        self.fb.forget_cursor();
+    }
+}
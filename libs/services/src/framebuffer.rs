@@ -0,0 +1,100 @@
+/// Ukuran framebuffer maksimum (panel 40x2 / 20x4)
+pub const MAX_COLS: usize = 40;
+pub const MAX_ROWS: usize = 4;
+
+type Frame = [[u8; MAX_COLS]; MAX_ROWS];
+
+const BLANK: Frame = [[b' '; MAX_COLS]; MAX_ROWS];
+
+/// Shadow framebuffer LCD karakter: isi yang diinginkan (`frame`) vs isi
+/// yang sudah ada di panel (`shown`). Dipakai display blocking dan async.
+#[derive(Clone, Copy)]
+pub(crate) struct FrameBuffer {
+    cols: u8,
+    rows: u8,
+    frame: Frame,
+    shown: Frame,
+    // posisi address counter LCD, bila diketahui
+    cursor: Option<(u8, u8)>,
+}
+
+impl FrameBuffer {
+    pub fn new(cols: u8, rows: u8) -> Self {
+        Self {
+            cols: cols.min(MAX_COLS as u8),
+            rows: rows.min(MAX_ROWS as u8),
+            frame: BLANK,
+            shown: BLANK,
+            cursor: None,
+        }
+    }
+
+    pub fn cols(&self) -> u8 {
+        self.cols
+    }
+
+    pub fn rows(&self) -> u8 {
+        self.rows
+    }
+
+    // ───────── isi ─────────
+
+    pub fn clear(&mut self) {
+        self.frame = BLANK;
+    }
+
+    pub fn clear_row(&mut self, row: u8) {
+        if row < self.rows {
+            self.frame[row as usize].fill(b' ');
+        }
+    }
+
+    /// Tulis mulai (col, row), dipotong di tepi kanan
+    pub fn put(&mut self, col: u8, row: u8, bytes: &[u8]) {
+        if row >= self.rows {
+            return;
+        }
+
+        let line = &mut self.frame[row as usize][..self.cols as usize];
+        for (cell, &b) in line.iter_mut().skip(col as usize).zip(bytes) {
+            *cell = b;
+        }
+    }
+
+    pub fn row(&self, row: u8) -> &[u8] {
+        match self.frame.get(row as usize) {
+            Some(line) if row < self.rows => &line[..self.cols as usize],
+            _ => &[],
+        }
+    }
+
+    // ───────── sinkronisasi panel ─────────
+
+    /// Panel baru saja di-clear: kosong, address counter di (0, 0)
+    pub fn panel_cleared(&mut self) {
+        self.shown = BLANK;
+        self.cursor = Some((0, 0));
+    }
+
+    /// Posisi address counter tidak diketahui lagi
+    pub fn forget_cursor(&mut self) {
+        self.cursor = None;
+    }
+
+    /// Byte yang harus dikirim ke (col, row), bila berbeda dari panel
+    pub fn pending(&self, col: u8, row: u8) -> Option<u8> {
+        let want = self.frame[row as usize][col as usize];
+        (self.shown[row as usize][col as usize] != want).then_some(want)
+    }
+
+    /// Perlu set DDRAM address sebelum menulis (col, row)
+    pub fn needs_move(&self, col: u8, row: u8) -> bool {
+        self.cursor != Some((col, row))
+    }
+
+    /// Catat satu sel yang sudah terkirim; address counter maju satu
+    pub fn written(&mut self, col: u8, row: u8, byte: u8) {
+        self.shown[row as usize][col as usize] = byte;
+        self.cursor = (col + 1 < self.cols).then_some((col + 1, row));
+    }
+}
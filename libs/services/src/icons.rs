@@ -0,0 +1,46 @@
+/// Glyph 5x8 bernama untuk CGRAM LCD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Icon {
+    pub name: &'static str,
+    pub bitmap: [u8; 8],
+}
+
+pub const DEGREE: Icon = Icon {
+    name: "degree",
+    bitmap: [0b00110, 0b01001, 0b01001, 0b00110, 0, 0, 0, 0],
+};
+
+pub const WIFI_OFF: Icon = Icon {
+    name: "wifi_off",
+    bitmap: [0, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0, 0],
+};
+
+pub const WIFI_LOW: Icon = Icon {
+    name: "wifi_low",
+    bitmap: [0, 0, 0, 0, 0, 0, 0b10000, 0b10000],
+};
+
+pub const WIFI_MID: Icon = Icon {
+    name: "wifi_mid",
+    bitmap: [0, 0, 0, 0, 0b00100, 0b00100, 0b10100, 0b10100],
+};
+
+pub const WIFI_HIGH: Icon = Icon {
+    name: "wifi_high",
+    bitmap: [0, 0, 0b00001, 0b00001, 0b00101, 0b00101, 0b10101, 0b10101],
+};
+
+pub const BATTERY_EMPTY: Icon = Icon {
+    name: "battery_empty",
+    bitmap: [0b01110, 0b11011, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11111],
+};
+
+pub const BATTERY_HALF: Icon = Icon {
+    name: "battery_half",
+    bitmap: [0b01110, 0b11011, 0b10001, 0b10001, 0b11111, 0b11111, 0b11111, 0b11111],
+};
+
+pub const BATTERY_FULL: Icon = Icon {
+    name: "battery_full",
+    bitmap: [0b01110, 0b11111, 0b11111, 0b11111, 0b11111, 0b11111, 0b11111, 0b11111],
+};
@@ -0,0 +1,137 @@
+use core::iter;
+
+use heapless::String;
+
+/// Perataan teks dalam satu baris
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Tepat `width` karakter: teks dipotong bila terlalu panjang,
+/// sisanya diisi spasi sesuai perataan.
+pub fn align(text: &str, width: usize, align: Align) -> impl Iterator<Item = char> + '_ {
+    let len = text.chars().count().min(width);
+    let pad = match align {
+        Align::Left => 0,
+        Align::Center => (width - len) / 2,
+        Align::Right => width - len,
+    };
+
+    iter::repeat_n(' ', pad)
+        .chain(text.chars().take(len))
+        .chain(iter::repeat(' '))
+        .take(width)
+}
+
+/// Pecah teks menjadi baris selebar `width` karakter, memotong di spasi.
+/// Kata yang lebih panjang dari `width` dipotong paksa; '\n' memaksa baris baru.
+pub fn wrap(text: &str, width: usize) -> Wrap<'_> {
+    Wrap { rest: text, width }
+}
+
+pub struct Wrap<'a> {
+    rest: &'a str,
+    width: usize,
+}
+
+impl<'a> Iterator for Wrap<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.width == 0 {
+            return None;
+        }
+
+        let text = self.rest.trim_start_matches(' ');
+        if text.is_empty() {
+            return None;
+        }
+
+        // batas `width` karakter (dalam byte), atau akhir teks / newline
+        let limit = text.char_indices().nth(self.width).map_or(text.len(), |(i, _)| i);
+        if let Some(nl) = text[..limit].find('\n') {
+            self.rest = &text[nl + 1..];
+            return Some(text[..nl].trim_end());
+        }
+
+        let fits = limit == text.len() || text[limit..].starts_with([' ', '\n']);
+        let end = if fits {
+            limit
+        } else {
+            text[..limit].rfind(' ').unwrap_or(limit)
+        };
+
+        let rest = &text[end..];
+        self.rest = rest.strip_prefix('\n').unwrap_or(rest);
+        Some(text[..end].trim_end())
+    }
+}
+
+/// Kapasitas teks marquee (byte UTF-8)
+pub const MARQUEE_LEN: usize = 64;
+
+/// Jarak antara ujung dan awal teks saat berputar
+const MARQUEE_GAP: &str = "   ";
+
+/// Teks berjalan untuk string yang lebih panjang dari lebar baris.
+/// Non-blocking: `tick(now_ms)` menggeser satu karakter tiap `step_ms`.
+#[derive(Debug, Clone)]
+pub struct Marquee {
+    text: String<MARQUEE_LEN>,
+    step_ms: u64,
+    offset: usize,
+    last_ms: Option<u64>,
+}
+
+impl Marquee {
+    /// Teks yang melebihi kapasitas dipotong di batas karakter
+    pub fn new(text: &str, step_ms: u64) -> Self {
+        let mut buf = String::new();
+        for c in text.chars() {
+            if buf.push(c).is_err() {
+                break;
+            }
+        }
+
+        Self {
+            text: buf,
+            step_ms,
+            offset: 0,
+            last_ms: None,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Geser bila sudah waktunya; `true` bila tampilan berubah
+    pub fn tick(&mut self, now_ms: u64) -> bool {
+        let Some(last) = self.last_ms else {
+            self.last_ms = Some(now_ms);
+            return false;
+        };
+
+        if now_ms.saturating_sub(last) < self.step_ms {
+            return false;
+        }
+
+        let period = self.text.chars().count() + MARQUEE_GAP.len();
+        self.offset = (self.offset + 1) % period;
+        self.last_ms = Some(now_ms);
+        true
+    }
+
+    /// Potongan `width` karakter yang sedang terlihat
+    pub fn window(&self, width: usize) -> impl Iterator<Item = char> + '_ {
+        self.text
+            .chars()
+            .chain(MARQUEE_GAP.chars())
+            .cycle()
+            .skip(self.offset)
+            .take(width)
+    }
+}
@@ -4,175 +4,866 @@
 extern crate std;
 
 pub mod display;
+#[cfg(feature = "async")]
+pub mod display_async;
+mod framebuffer;
+pub mod icons;
+pub mod layout;
+pub mod menu;
+pub mod segment_display;
+pub mod text_display;
 pub mod wifi;
 pub mod wifi_config;
+pub mod widgets;
 
 pub use display::LcdDisplay;
+#[cfg(feature = "async")]
+pub use display_async::LcdDisplayAsync;
+pub use framebuffer::{MAX_COLS, MAX_ROWS};
+pub use layout::Align;
+pub use menu::{Menu, MenuEvent, MenuInput, MenuItem, MenuModel, MenuView};
+pub use segment_display::SegmentDisplay;
+pub use text_display::TextDisplay;
 pub use wifi::WifiService;
+pub use widgets::Region;
 pub use wifi_config::load_from_fs;
 // ================= UNIT TESTS =================
 
 #[cfg(test)]
 mod tests {
-    use super::LcdDisplay;
-    use drivers::LcdI2c;
+    use super::{Align, LcdDisplay, Region, TextDisplay};
+    use super::{icons, layout};
+    use drivers::{LcdError, LcdGeometry, LcdI2c};
 
-    use embedded_hal::i2c::{
-        I2c, SevenBitAddress, ErrorType as I2cErrorType, Operation,
-    };
-    use embedded_hal::delay::DelayNs;
+    use testkit::{MockDelay, MockI2c, Pcf8574Lcd};
+
+    // ================= TEST DISPLAY =================
+
+    #[test]
+    fn display_boot_screen_writes_i2c() {
+        let i2c = MockI2c::new();
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(i2c.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .expect("lcd init failed");
+
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+
+        let before = i2c.count();
+        display.boot_screen();
+        display.flush();
+        let after = i2c.count();
+
+        assert!(after > before);
+    }
+
+    #[test]
+    fn display_show_message_writes_i2c() {
+        let i2c = MockI2c::new();
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(i2c.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .expect("lcd init failed");
+
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+
+        let before = i2c.count();
+        display.show_message(0, "Hello");
+        display.show_message(1, "ESP32");
+        display.flush();
+
+        let after = i2c.count();
+
+        assert!(after > before);
+    }
+
+    #[test]
+    fn display_boot_screen_shows_text() {
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .expect("lcd init failed");
 
-    use core::convert::Infallible;
-    use std::cell::RefCell;
-    use std::rc::Rc;
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+        display.boot_screen();
+        display.flush();
 
-    // ===== mock delay =====
-    struct TestDelay;
-    impl DelayNs for TestDelay {
-        fn delay_ns(&mut self, _ns: u32) {}
+        assert_eq!(bus.lines(), ["ESP32-C3 Mini   ", "System Ready    "]);
     }
 
-    // ===== mock i2c =====
-    #[derive(Clone)]
-    struct TestI2c {
-        writes: Rc<RefCell<u32>>,
+    #[test]
+    fn display_show_led_overwrites_second_row() {
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .expect("lcd init failed");
+
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+        display.boot_screen();
+        display.show_led(true);
+        display.flush();
+
+        assert_eq!(bus.lines(), ["ESP32-C3 Mini   ", "LED: ON eady    "]);
     }
 
-    impl TestI2c {
-        fn new() -> Self {
-            Self {
-                writes: Rc::new(RefCell::new(0)),
-            }
+    #[test]
+    fn display_clear_row_uses_driver_width() {
+        let bus = Pcf8574Lcd::new(0x27, 20, 4);
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_20X4, &mut delay)
+            .expect("lcd init failed");
+
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+        for row in 0..4 {
+            display.show_message(row, "XXXXXXXXXXXXXXXXXXXX");
         }
+        display.clear_row(3);
+        display.flush();
+
+        assert_eq!(display.cols(), 20);
+        assert_eq!(bus.screen().row(2), "XXXXXXXXXXXXXXXXXXXX");
+        assert_eq!(bus.screen().row(3), " ".repeat(20));
+    }
+
+    #[test]
+    fn display_icons_share_slot_by_name() {
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .expect("lcd init failed");
+
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+        display.show_message(0, "21 C");
+        assert!(display.show_icon(2, 0, &icons::DEGREE));
+        assert!(display.show_icon(15, 1, &icons::WIFI_HIGH));
+        assert!(display.show_icon(15, 0, &icons::DEGREE));
+        display.flush();
+
+        let screen = bus.screen();
+        assert_eq!(&screen.cells(0)[..4], &[b'2', b'1', 0, b'C']);
+        assert_eq!(screen.cells(0)[15], 0);
+        assert_eq!(screen.cells(1)[15], 1);
+        assert_eq!(screen.glyph(0), icons::DEGREE.bitmap);
+        assert_eq!(screen.glyph(1), icons::WIFI_HIGH.bitmap);
+    }
 
-        fn count(&self) -> u32 {
-            *self.writes.borrow()
+    #[test]
+    fn display_reinitialises_lcd_after_hot_plug() {
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .expect("lcd init failed");
+
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+        display.register_icon(&icons::DEGREE);
+
+        bus.set_present(false);
+        display.show_message(0, "lost");
+        display.flush();
+        assert!(!display.is_online());
+        assert!(matches!(display.last_error(), Some(LcdError::Nack(_))));
+
+        bus.set_present(true);
+        display.show_message(0, "back");
+        display.flush();
+        assert!(display.is_online());
+        assert_eq!(bus.screen().row(0), "back            ");
+        assert_eq!(bus.screen().glyph(0), icons::DEGREE.bitmap);
+    }
+
+    #[test]
+    fn display_initialises_lcd_plugged_in_after_boot() {
+        use drivers::Backpack;
+
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        bus.set_present(false);
+
+        let lcd = LcdI2c::uninit(bus.clone(), 0x27, LcdGeometry::LCD_16X2, Backpack::default());
+        let mut display = LcdDisplay::deferred(lcd, MockDelay::new());
+        assert!(!display.is_online());
+
+        display.init();
+        display.show_message(0, "waiting");
+        display.flush();
+        assert!(!display.is_online());
+        assert!(matches!(display.last_error(), Some(LcdError::Nack(_))));
+
+        bus.set_present(true);
+        display.flush();
+        assert!(display.is_online());
+        assert_eq!(bus.lines(), ["waiting         ", "                "]);
+    }
+
+    #[test]
+    fn display_resyncs_after_bus_error() {
+        use embedded_hal::i2c::ErrorKind;
+
+        let i2c = MockI2c::new();
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(i2c.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .expect("lcd init failed");
+
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+        i2c.clear();
+        // nibble tinggi set_cursor sudah di-latch, nibble rendah gagal
+        i2c.fail_after(2, ErrorKind::Bus);
+        display.show_message(0, "Hi");
+        display.flush();
+
+        assert!(display.is_online());
+        assert!(display.last_error().is_none());
+        // controller disinkronkan ulang (0x3, 0x3, 0x3, 0x2) sebelum flush diulang
+        let written = i2c.written();
+        assert_eq!(written[2..10], [0x3C, 0x38, 0x3C, 0x38, 0x3C, 0x38, 0x2C, 0x28]);
+        // ... lalu "Hi" dikirim ulang di akhir
+        assert_eq!(written[written.len() - 8..], [0x4D, 0x49, 0x8D, 0x89, 0x6D, 0x69, 0x9D, 0x99]);
+    }
+
+    #[test]
+    fn display_flush_sends_only_changed_cells() {
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        let i2c = MockI2c::new();
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(i2c.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .expect("lcd init failed");
+        let mut display = LcdDisplay::new(Some(lcd), delay.clone());
+        display.init();
+        display.show_message(1, "Connected.");
+        display.flush();
+
+        // ganti "Connected." -> "Connected!": 1 set_cursor + 1 karakter
+        i2c.clear();
+        display.clear_row(1);
+        display.show_message(1, "Connected!");
+        display.flush();
+        assert_eq!(i2c.count(), 8);
+
+        // tidak ada perubahan -> tidak ada trafik
+        i2c.clear();
+        display.flush();
+        assert_eq!(i2c.count(), 0);
+
+        // hasil akhirnya sama dengan panel emulator
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay).unwrap();
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+        display.init();
+        display.show_message(0, "ab  cd");
+        display.flush();
+        display.show_message(0, "xb  cy");
+        display.flush();
+        assert_eq!(bus.lines(), ["xb  cy          ", "                "]);
+    }
+
+    // ================= TEST LAYOUT =================
+
+    fn display_16x2() -> (Pcf8574Lcd, LcdDisplay<Pcf8574Lcd, MockDelay>) {
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .expect("lcd init failed");
+
+        (bus, LcdDisplay::new(Some(lcd), delay))
+    }
+
+    #[test]
+    fn layout_align_pads_and_truncates() {
+        let s: std::string::String = layout::align("abc", 7, Align::Center).collect();
+        assert_eq!(s, "  abc  ");
+
+        let s: std::string::String = layout::align("abc", 5, Align::Right).collect();
+        assert_eq!(s, "  abc");
+
+        let s: std::string::String = layout::align("abcdef", 4, Align::Left).collect();
+        assert_eq!(s, "abcd");
+    }
+
+    #[test]
+    fn layout_wrap_breaks_at_words() {
+        let lines: std::vec::Vec<&str> =
+            layout::wrap("SSID OfficeWiFi-5GHz connected", 10).collect();
+        assert_eq!(lines, ["SSID", "OfficeWiFi", "-5GHz", "connected"]);
+
+        let lines: std::vec::Vec<&str> = layout::wrap("IP 192.168.100.200", 8).collect();
+        assert_eq!(lines, ["IP", "192.168.", "100.200"]);
+
+        let lines: std::vec::Vec<&str> = layout::wrap("line one\nline two", 16).collect();
+        assert_eq!(lines, ["line one", "line two"]);
+    }
+
+    #[test]
+    fn display_show_aligned_clears_stale_chars() {
+        let (bus, mut display) = display_16x2();
+
+        display.show_message(1, "Connecting...");
+        display.show_aligned(1, "OK", Align::Right);
+        display.show_aligned(0, "WiFi", Align::Center);
+        display.flush();
+
+        assert_eq!(bus.lines(), ["      WiFi      ", "              OK"]);
+    }
+
+    #[test]
+    fn display_show_wrapped_spans_rows() {
+        let (bus, mut display) = display_16x2();
+
+        let used = display.show_wrapped(0, "Connected to OfficeWiFi-Lantai2 network");
+        display.flush();
+
+        assert_eq!(used, 2);
+        assert_eq!(bus.lines(), ["Connected to    ", "OfficeWiFi-Lanta"]);
+    }
+
+    #[test]
+    fn display_marquee_scrolls_on_tick() {
+        let (bus, mut display) = display_16x2();
+
+        display.show_marquee(1, "IP 192.168.100.200", 300);
+        display.flush();
+        assert_eq!(bus.screen().row(1), "IP 192.168.100.2");
+
+        assert!(!display.tick(0));
+        assert!(!display.tick(299));
+        assert!(display.tick(300));
+        display.flush();
+        assert_eq!(bus.screen().row(1), "P 192.168.100.20");
+
+        // putaran penuh: 18 karakter + 3 spasi
+        for step in 2..=21 {
+            display.tick(step * 300);
         }
+        display.flush();
+        assert_eq!(bus.screen().row(1), "IP 192.168.100.2");
+
+        display.show_message(1, "stop");
+        assert!(!display.tick(10_000));
     }
 
-    impl I2cErrorType for TestI2c {
-        type Error = Infallible;
+    #[test]
+    fn display_maps_utf8_to_lcd_rom() {
+        let (bus, mut display) = display_16x2();
+
+        display.show_aligned(0, "21.5°C", Align::Right);
+        display.flush();
+
+        assert_eq!(&bus.screen().cells(0)[10..], &[b'2', b'1', b'.', b'5', 0xDF, b'C']);
     }
 
-    impl I2c<SevenBitAddress> for TestI2c {
-        fn write(
-            &mut self,
-            _addr: SevenBitAddress,
-            _bytes: &[u8],
-        ) -> Result<(), Self::Error> {
-            *self.writes.borrow_mut() += 1;
-            Ok(())
+    #[test]
+    fn display_screen_saver_dims_after_idle() {
+        let (bus, mut display) = display_16x2();
+        display.set_screen_saver(Some(10_000));
+
+        display.show_message(0, "idle");
+        display.flush();
+        display.tick(1_000);
+        display.tick(10_999);
+        assert!(bus.screen().backlight());
+
+        display.tick(11_000);
+        assert!(display.is_asleep());
+        assert!(!bus.screen().backlight());
+
+        // flush tanpa perubahan tidak membangunkan
+        display.flush();
+        assert!(display.is_asleep());
+
+        display.show_message(0, "busy");
+        display.flush();
+        assert!(!display.is_asleep());
+        assert!(bus.screen().backlight());
+    }
+
+    // ================= TEST TEXT DISPLAY =================
+
+    /// Layar teks minimal untuk menguji default method trait
+    struct Console(Vec<String>);
+
+    impl TextDisplay for Console {
+        fn cols(&self) -> u8 {
+            8
         }
 
-        fn read(
-            &mut self,
-            _addr: SevenBitAddress,
-            _buf: &mut [u8],
-        ) -> Result<(), Self::Error> {
-            Ok(())
+        fn rows(&self) -> u8 {
+            self.0.len() as u8
         }
 
-        fn write_read(
-            &mut self,
-            _addr: SevenBitAddress,
-            _bytes: &[u8],
-            _buf: &mut [u8],
-        ) -> Result<(), Self::Error> {
-            Ok(())
+        fn write_at(&mut self, col: u8, row: u8, text: &str) {
+            let line = &mut self.0[row as usize];
+            let tail: String = line.chars().skip(col as usize + text.len()).collect();
+            *line = format!("{}{}{}", &line[..col as usize], text, tail);
+            line.truncate(8);
         }
 
-        fn transaction(
-            &mut self,
-            _addr: SevenBitAddress,
-            ops: &mut [Operation<'_>],
-        ) -> Result<(), Self::Error> {
-            for op in ops {
-                if let Operation::Write(_) = op {
-                    *self.writes.borrow_mut() += 1;
-                }
-            }
-            Ok(())
+        fn clear(&mut self) {
+            self.0.iter_mut().for_each(|l| *l = " ".repeat(8));
         }
+
+        fn flush(&mut self) {}
     }
 
-    // ================= TEST DISPLAY =================
+    #[test]
+    fn text_display_write_line_pads_row() {
+        let mut console = Console(vec![" ".repeat(8); 2]);
+
+        console.write_at(0, 0, "abcdefgh");
+        console.write_line(0, "hi", Align::Center);
+        assert_eq!(console.0[0], "   hi   ");
+    }
 
     #[test]
-    fn display_boot_screen_writes_i2c() {
-        let i2c = TestI2c::new();
-        let mut delay = TestDelay;
+    fn lcd_display_works_as_text_display() {
+        let (bus, mut display) = display_16x2();
+        let text: &mut dyn TextDisplay = &mut display;
+
+        text.write_at(4, 0, "temp");
+        text.write_line(1, "ok", Align::Right);
+        text.flush();
+        assert_eq!(bus.lines(), ["    temp        ", "              ok"]);
+
+        text.clear();
+        text.flush();
+        assert_eq!(bus.lines(), [" ".repeat(16), " ".repeat(16)]);
+    }
 
-        let lcd = LcdI2c::new(i2c.clone(), 0x27, &mut delay)
-            .expect("lcd init failed");
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn oled_text_works_as_text_display() {
+        use drivers::{Oled, OledController, OledText};
+        use testkit::OledBus;
+
+        let bus = OledBus::ssd1306(0x3C, 32);
+        let mut oled = Oled::new(bus.clone(), 0x3C, OledController::Ssd1306, 32);
+        oled.init().unwrap();
+
+        let mut text = OledText::new(oled);
+        let display: &mut dyn TextDisplay = &mut text;
+        assert_eq!((display.cols(), display.rows()), (21, 4));
+
+        display.write_line(3, "ok", Align::Right);
+        display.flush();
+        let lit = |x0: usize| (24..32).any(|y| (x0..x0 + 6).any(|x| bus.panel().pixel(x, y)));
+        assert!(lit(19 * 6) && lit(20 * 6));
+        assert!(!lit(0));
+    }
+
+    #[test]
+    fn segment_display_works_as_text_display() {
+        use super::SegmentDisplay;
+        use drivers::Tm1637;
+        use testkit::Tm1637Chip;
+
+        let chip = Tm1637Chip::new();
+        let mut tm = Tm1637::new(chip.clk(), chip.dio(), MockDelay::new(), 4);
+        tm.init().unwrap();
+
+        let mut segments = SegmentDisplay::new(tm);
+        let display: &mut dyn TextDisplay = &mut segments;
+        assert_eq!((display.cols(), display.rows()), (4, 1));
+
+        display.write_line(0, "2.5", Align::Right);
+        display.flush();
+        // titik menempel di digit 2, tidak memakan kolom
+        assert_eq!(chip.segments()[..4], [0x00, 0x00, 0x5B | 0x80, 0x6D]);
+
+        let commands = chip.commands().len();
+        display.flush();
+        assert_eq!(chip.commands().len(), commands);
+    }
+
+    #[test]
+    fn segment_display_retries_failed_flush() {
+        use super::SegmentDisplay;
+        use drivers::Tm1637;
+        use testkit::Tm1637Chip;
+
+        let chip = Tm1637Chip::new();
+        let mut display = SegmentDisplay::new(Tm1637::new(chip.clk(), chip.dio(), MockDelay::new(), 4));
+
+        chip.set_present(false);
+        display.write_at(0, 0, "88");
+        display.flush();
+        assert!(!display.is_online());
+
+        chip.set_present(true);
+        display.flush();
+        assert!(display.is_online());
+        assert_eq!(chip.segments()[..2], [0x7F, 0x7F]);
+    }
+
+    // ================= TEST MENU =================
+
+    use super::menu::{ButtonRole, ItemKind, Menu, MenuEvent, MenuInput, MenuItem, MenuModel, MenuView};
+
+    const BRIGHTNESS: u16 = 1;
+    const BACKLIGHT: u16 = 2;
+    const REBOOT: u16 = 3;
+    const SSID: u16 = 4;
+
+    static NETWORK: Menu = Menu {
+        title: "Network",
+        items: &[
+            MenuItem { label: "SSID", kind: ItemKind::Text { id: SSID, max_len: 4, charset: "ABC" } },
+            MenuItem { label: "Back", kind: ItemKind::Back },
+        ],
+    };
+
+    static SETTINGS: Menu = Menu {
+        title: "Settings",
+        items: &[
+            MenuItem {
+                label: "Brightness",
+                kind: ItemKind::Number { id: BRIGHTNESS, min: 0, max: 100, step: 10, unit: "%" },
+            },
+            MenuItem { label: "Backlight", kind: ItemKind::Toggle(BACKLIGHT) },
+            MenuItem { label: "Network", kind: ItemKind::Submenu(&NETWORK) },
+            MenuItem { label: "Reboot", kind: ItemKind::Action(REBOOT) },
+        ],
+    };
 
+    #[test]
+    fn menu_depth_is_checked_against_stack() {
+        static L7: Menu = Menu { title: "7", items: &[] };
+        static L6: Menu = Menu { title: "6", items: &[MenuItem { label: "7", kind: ItemKind::Submenu(&L7) }] };
+        static L5: Menu = Menu { title: "5", items: &[MenuItem { label: "6", kind: ItemKind::Submenu(&L6) }] };
+        static L4: Menu = Menu { title: "4", items: &[MenuItem { label: "5", kind: ItemKind::Submenu(&L5) }] };
+        static L3: Menu = Menu { title: "3", items: &[MenuItem { label: "4", kind: ItemKind::Submenu(&L4) }] };
+        static L2: Menu = Menu { title: "2", items: &[MenuItem { label: "3", kind: ItemKind::Submenu(&L3) }] };
+        static ROOT: Menu = Menu { title: "1", items: &[MenuItem { label: "2", kind: ItemKind::Submenu(&L2) }] };
+
+        const _: () = assert!(SETTINGS.depth() == 2);
+        assert_eq!(L2.depth(), super::menu::DEPTH);
+        assert_eq!(ROOT.depth(), super::menu::DEPTH + 1);
+
+        let deep = std::panic::catch_unwind(|| MenuView::new(&ROOT));
+        assert!(deep.is_err());
+    }
+
+    #[derive(Default)]
+    struct Settings {
+        brightness: i32,
+        backlight: bool,
+        ssid: String,
+    }
+
+    impl MenuModel for Settings {
+        fn number(&self, _id: u16) -> i32 {
+            self.brightness
+        }
+
+        fn set_number(&mut self, _id: u16, value: i32) {
+            self.brightness = value;
+        }
+
+        fn toggle(&self, _id: u16) -> bool {
+            self.backlight
+        }
+
+        fn set_toggle(&mut self, _id: u16, on: bool) {
+            self.backlight = on;
+        }
+
+        fn text(&self, _id: u16) -> &str {
+            &self.ssid
+        }
+
+        fn set_text(&mut self, _id: u16, text: &str) {
+            self.ssid = text.into();
+        }
+    }
+
+    fn show(menu: &mut MenuView, display: &mut LcdDisplay<Pcf8574Lcd, MockDelay>, model: &Settings) {
+        menu.render(display, model);
+        display.flush();
+    }
+
+    #[test]
+    fn menu_renders_title_and_scrolls_viewport() {
+        let (bus, mut display) = display_16x2();
+        let model = Settings { brightness: 50, ..Settings::default() };
+        let mut menu = MenuView::new(&SETTINGS);
+
+        show(&mut menu, &mut display, &model);
+        assert_eq!(bus.lines(), ["Settings        ", ">Brightness  50%"]);
+
+        menu.handle(MenuInput::Move(-2), &mut Settings::default());
+        show(&mut menu, &mut display, &model);
+        // submenu ditandai panah kanan (ROM A00 0x7E)
+        assert_eq!(bus.lines()[1], ">Network       >");
+    }
+
+    #[test]
+    fn menu_viewport_follows_cursor_on_taller_display() {
+        let bus = Pcf8574Lcd::new(0x27, 20, 4);
+        let mut delay = MockDelay::new();
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_20X4, &mut delay).ok();
         let mut display = LcdDisplay::new(lcd, delay);
 
-        let before = i2c.count();
-        display.boot_screen();
-        let after = i2c.count();
+        let mut model = Settings::default();
+        let mut menu = MenuView::new(&SETTINGS);
+        menu.handle(MenuInput::Move(3), &mut model);
+        show(&mut menu, &mut display, &model);
 
-        assert!(after > before);
+        let lines = bus.lines();
+        assert_eq!(lines[1], " Backlight       OFF");
+        assert_eq!(lines[3].trim_end(), ">Reboot");
+
+        menu.handle(MenuInput::Move(1), &mut model);
+        show(&mut menu, &mut display, &model);
+        assert_eq!(bus.lines()[1], ">Brightness       0%");
     }
 
     #[test]
-    fn display_show_message_writes_i2c() {
-        let i2c = TestI2c::new();
-        let mut delay = TestDelay;
+    fn menu_number_editor_clamps_and_commits() {
+        let (bus, mut display) = display_16x2();
+        let mut model = Settings { brightness: 50, ..Settings::default() };
+        let mut menu = MenuView::new(&SETTINGS);
+
+        assert_eq!(menu.handle(MenuInput::Select, &mut model), None);
+        assert!(menu.is_editing());
+        menu.handle(MenuInput::Move(2), &mut model);
+        show(&mut menu, &mut display, &model);
+        assert_eq!(bus.lines()[1], ">Brightness[70%]");
+
+        // batal: model tidak berubah
+        menu.handle(MenuInput::Back, &mut model);
+        assert_eq!(model.brightness, 50);
+
+        menu.handle(MenuInput::Select, &mut model);
+        menu.handle(MenuInput::Move(9), &mut model);
+        assert_eq!(menu.handle(MenuInput::Select, &mut model), Some(MenuEvent::Changed(BRIGHTNESS)));
+        assert_eq!(model.brightness, 100);
+    }
 
-        let lcd = LcdI2c::new(i2c.clone(), 0x27, &mut delay)
-            .expect("lcd init failed");
+    #[test]
+    fn menu_toggle_and_action_report_events() {
+        let mut model = Settings::default();
+        let mut menu = MenuView::new(&SETTINGS);
 
-        let mut display = LcdDisplay::new(lcd, delay);
+        menu.handle(MenuInput::Move(1), &mut model);
+        assert_eq!(menu.handle(MenuInput::Select, &mut model), Some(MenuEvent::Changed(BACKLIGHT)));
+        assert!(model.backlight);
 
-        let before = i2c.count();
-        display.show_message(0, "Hello");
-        display.show_message(1, "ESP32");
+        menu.handle(MenuInput::Move(2), &mut model);
+        assert_eq!(menu.handle(MenuInput::Select, &mut model), Some(MenuEvent::Action(REBOOT)));
+        assert_eq!(menu.handle(MenuInput::Back, &mut model), Some(MenuEvent::Exit));
+    }
 
-        let after = i2c.count();
+    #[test]
+    fn menu_text_entry_with_char_wheel() {
+        let (bus, mut display) = display_16x2();
+        let mut model = Settings::default();
+        let mut menu = MenuView::new(&SETTINGS);
+
+        menu.handle(MenuInput::Move(2), &mut model);
+        menu.handle(MenuInput::Select, &mut model);
+        assert_eq!(menu.menu().title, "Network");
+        menu.handle(MenuInput::Select, &mut model);
+
+        // roda: A B C selesai hapus
+        for input in [MenuInput::Select, MenuInput::Move(2), MenuInput::Select] {
+            menu.handle(input, &mut model);
+        }
+        show(&mut menu, &mut display, &model);
+        assert_eq!(bus.lines(), ["Network         ", ">SSID      AC[C]"]);
+
+        menu.handle(MenuInput::Move(2), &mut model);
+        menu.handle(MenuInput::Select, &mut model);
+        menu.handle(MenuInput::Move(-1), &mut model);
+        assert_eq!(menu.handle(MenuInput::Select, &mut model), Some(MenuEvent::Changed(SSID)));
+        assert_eq!(model.ssid, "A");
+
+        menu.handle(MenuInput::Move(1), &mut model);
+        assert_eq!(menu.handle(MenuInput::Select, &mut model), None);
+        assert_eq!(menu.depth(), 1);
+    }
 
-        assert!(after > before);
+    #[test]
+    fn menu_text_entry_keeps_long_value() {
+        use super::menu::TEXT_MAX;
+
+        static WIFI: Menu = Menu {
+            title: "WiFi",
+            items: &[MenuItem { label: "Pass", kind: ItemKind::Text { id: SSID, max_len: 200, charset: "AB" } }],
+        };
+
+        let passphrase = "0123456789".repeat(4);
+        let mut model = Settings { ssid: passphrase.clone(), ..Settings::default() };
+        let mut menu = MenuView::new(&WIFI);
+
+        // nilai 40 karakter terbuka utuh; roda: A B selesai hapus
+        menu.handle(MenuInput::Select, &mut model);
+        menu.handle(MenuInput::Select, &mut model);
+        menu.handle(MenuInput::Move(2), &mut model);
+        assert_eq!(menu.handle(MenuInput::Select, &mut model), Some(MenuEvent::Changed(SSID)));
+        assert_eq!(model.ssid, format!("{passphrase}A"));
+
+        // max_len di atas kapasitas buffer dibatasi TEXT_MAX
+        menu.handle(MenuInput::Select, &mut model);
+        for _ in 0..40 {
+            menu.handle(MenuInput::Select, &mut model);
+        }
+        menu.handle(MenuInput::Move(2), &mut model);
+        menu.handle(MenuInput::Select, &mut model);
+        assert_eq!(model.ssid.len(), TEXT_MAX);
+        assert!(model.ssid.starts_with(&passphrase));
     }
 
+    #[test]
+    fn menu_input_maps_buttons_and_encoder() {
+        use drivers::{ButtonEvent, EncoderEvent};
 
-    // // ================= TEST WIFI =================
+        assert_eq!(MenuInput::from_encoder(EncoderEvent::Turn(-3)), Some(MenuInput::Move(-3)));
+        assert_eq!(MenuInput::from_encoder(EncoderEvent::Button(ButtonEvent::Click)), Some(MenuInput::Select));
+        assert_eq!(MenuInput::from_encoder(EncoderEvent::Button(ButtonEvent::Press)), None);
 
-    // #[derive(Default)]
-    // struct MockWifi {
-    //     connected: bool,
-    //     last_ssid: Option<String>,
-    // }
+        assert_eq!(MenuInput::from_button(ButtonRole::Down, ButtonEvent::Repeat), Some(MenuInput::Move(1)));
+        assert_eq!(MenuInput::from_button(ButtonRole::Single, ButtonEvent::LongPress), Some(MenuInput::Select));
+        assert_eq!(MenuInput::from_button(ButtonRole::Select, ButtonEvent::Release), None);
+    }
 
-    // impl Wifi for MockWifi {
-    //     fn connect(&mut self, ssid: &str, _password: &str) {
-    //         self.connected = true;
-    //         self.last_ssid = Some(ssid.to_string());
-    //     }
+    // ================= TEST WIDGETS =================
+
+    /// Layar sebagai ASCII-art: glyph widget didekode ke simbol
+    fn decode_row(bus: &Pcf8574Lcd, row: usize) -> String {
+        let screen = bus.screen();
+        (0..screen.cols())
+            .map(|col| match screen.cell_glyph(col, row) {
+                None => screen.cells(row)[col] as char,
+                Some([0x1F, 0x1F, 0x1F, 0, 0, 0x1F, 0x1F, 0x1F]) => '=',
+                Some([0x1F, 0x1F, 0x1F, 0, 0, 0, 0, 0]) => '^',
+                Some([0, 0, 0, 0, 0, 0x1F, 0x1F, 0x1F]) => '_',
+                Some([0x1F, ..]) => '#',
+                Some([bits, ..]) if bits != 0 => char::from(b'0' + bits.count_ones() as u8),
+                Some(bitmap) => char::from(b'a' + bitmap.iter().filter(|&&b| b != 0).count() as u8 - 1),
+            })
+            .collect()
+    }
 
-    //     fn is_connected(&self) -> bool {
-    //         self.connected
-    //     }
-    // }
+    #[test]
+    fn widget_big_number_renders_right_aligned() {
+        let (bus, mut display) = display_16x2();
 
-    // #[test]
-    // fn wifi_connect_sets_connected_true() {
-    //     let mock = MockWifi::default();
-    //     let mut service = WifiService::new(mock);
+        assert!(display.show_big_number(Region::new(0, 0, 16, 2), "42"));
+        display.flush();
 
-    //     service.connect("MySSID", "secret");
+        assert_eq!(decode_row(&bus, 0), "         #_# ==#");
+        assert_eq!(decode_row(&bus, 1), "           # #__");
+        // region 1 baris tidak cukup
+        assert!(!display.show_big_number(Region::new(0, 1, 16, 1), "1"));
+    }
 
-    //     assert!(service.is_connected());
-    // }
+    #[test]
+    fn widget_bar_has_fifth_cell_resolution() {
+        let (bus, mut display) = display_16x2();
 
-    // #[test]
-    // fn wifi_connect_stores_ssid() {
-    //     let mock = MockWifi::default();
-    //     let mut service = WifiService::new(mock);
+        display.show_message(0, "Level");
+        assert!(display.show_bar(Region::new(6, 0, 10, 1), 50, 100));
+        assert!(display.show_bar(Region::new(0, 1, 16, 1), 33, 100));
+        display.flush();
 
-    //     service.connect("OfficeWiFi", "12345678");
+        assert_eq!(decode_row(&bus, 0), "Level #####     ");
+        // 33% dari 80 px = 26 px: 5 sel penuh + 1 kolom
+        assert_eq!(decode_row(&bus, 1), "#####1          ");
+    }
 
-    //     // akses ke mock via service (pattern: destructure)
-    //     let WifiService { wifi } = service;
-    //     assert_eq!(wifi.last_ssid.as_deref(), Some("OfficeWiFi"));
-    // }
-}
+    #[test]
+    fn widget_sparkline_spans_rows() {
+        let (bus, mut display) = display_16x2();
+
+        let values = [9, 0, 4, 8, 12, 16];
+        assert!(display.show_sparkline(Region::new(12, 0, 4, 2), &values, 16));
+        display.flush();
+
+        // level 0, 4, 8, 12, 16 dari 16; baris atas mulai level 8
+        assert_eq!(&decode_row(&bus, 0)[12..], "  d#");
+        assert_eq!(&decode_row(&bus, 1)[12..], "d###");
+    }
+
+    #[test]
+    fn widget_releases_glyphs_no_longer_shown() {
+        let bus = Pcf8574Lcd::new(0x27, 20, 4);
+        let mut delay = MockDelay::new();
+        let lcd = LcdI2c::new(bus.clone(), 0x27, LcdGeometry::LCD_20X4, &mut delay).unwrap();
+        let mut display = LcdDisplay::new(Some(lcd), delay);
+        display.show_icon(19, 0, &icons::DEGREE);
+
+        // sparkline 1 baris: BLOCK + spark1..6, ditambah icon = 8 slot
+        assert!(display.show_sparkline(Region::new(0, 3, 8, 1), &[1, 2, 3, 4, 5, 6, 8], 8));
+        assert!(!display.show_big_number(Region::new(0, 0, 12, 2), "42"));
+
+        // bar menimpa sparkline: slot spark dibebaskan, angka besar muat lagi
+        for value in [10, 20, 30, 40] {
+            assert!(display.show_bar(Region::new(0, 3, 8, 1), value, 40));
+        }
+        assert!(display.show_big_number(Region::new(0, 0, 12, 2), "42"));
+        display.flush();
+
+        assert_eq!(&decode_row(&bus, 1)[5..12], "  # #__");
+        assert_eq!(&decode_row(&bus, 3)[..8], "########");
+        assert_eq!(bus.screen().cell_glyph(19, 0), Some(icons::DEGREE.bitmap));
+    }
+
+    // ================= TEST DISPLAY ASYNC =================
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn display_async_flush_matches_blocking() {
+        use super::LcdDisplayAsync;
+        use drivers::LcdI2cAsync;
+
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        let mut delay = MockDelay::new();
+
+        let lcd = LcdI2cAsync::new(bus.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .await
+            .expect("lcd init failed");
+
+        let mut display = LcdDisplayAsync::new(Some(lcd), delay);
+        display.init().await;
+        display.show_message(0, "WiFi status:");
+        display.show_message(1, "Connecting...");
+        display.flush().await;
+
+        display.clear_row(1);
+        display.show_message(1, "Connected.");
+        display.flush().await;
+
+        assert_eq!(bus.lines(), ["WiFi status:    ", "Connected.      "]);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn display_async_recovers_after_hot_plug() {
+        use super::LcdDisplayAsync;
+        use drivers::LcdI2cAsync;
+
+        let bus = Pcf8574Lcd::new(0x27, 16, 2);
+        let mut delay = MockDelay::new();
 
+        let lcd = LcdI2cAsync::new(bus.clone(), 0x27, LcdGeometry::LCD_16X2, &mut delay)
+            .await
+            .expect("lcd init failed");
+
+        let mut display = LcdDisplayAsync::new(Some(lcd), delay);
+        display.show_message(0, "one");
+        display.flush().await;
+
+        bus.set_present(false);
+        display.show_message(1, "two");
+        display.flush().await;
+        assert!(!display.is_online());
+
+        bus.set_present(true);
+        display.flush().await;
+        assert!(display.is_online());
+        assert_eq!(bus.lines(), ["one             ", "two             "]);
+    }
+}
@@ -0,0 +1,434 @@
+//! Menu on-device: pohon statis (submenu, aksi, angka, toggle, teks),
+//! digambar ke `TextDisplay` dan dikendalikan event tombol/encoder.
+//!
+//! Nilai tidak disimpan di pohon; menu membaca dan menulis lewat `MenuModel`
+//! milik aplikasi.
+
+use core::fmt::Write;
+
+use drivers::{ButtonEvent, EncoderEvent};
+use heapless::{String, Vec};
+
+use crate::framebuffer::MAX_COLS;
+use crate::layout::{self, Align};
+use crate::text_display::TextDisplay;
+
+/// Id item yang dilaporkan ke aplikasi
+pub type ItemId = u16;
+
+/// Kedalaman submenu maksimum (root = 1)
+pub const DEPTH: usize = 6;
+/// Kapasitas teks yang diedit dalam byte (cukup untuk passphrase WPA)
+pub const TEXT_MAX: usize = 64;
+
+/// Tanda di kolom kanan: submenu, selesai edit teks, hapus karakter.
+/// ASCII agar tampil di semua ROM LCD, OLED dan TFT ('→' hanya ada di A00).
+const ARROW_RIGHT: char = '>';
+const ARROW_LEFT: char = '<';
+
+pub struct Menu {
+    pub title: &'static str,
+    pub items: &'static [MenuItem],
+}
+
+impl Menu {
+    /// Jumlah level pohon (root = 1); berhenti menghitung setelah `DEPTH + 1`.
+    /// Bisa dicek saat kompilasi: `const _: () = assert!(ROOT.depth() <= DEPTH);`
+    pub const fn depth(&self) -> usize {
+        self.depth_from(1)
+    }
+
+    const fn depth_from(&self, level: usize) -> usize {
+        // juga menghentikan submenu yang menunjuk balik ke leluhurnya
+        if level > DEPTH {
+            return level;
+        }
+
+        let mut deepest = level;
+        let mut i = 0;
+        while i < self.items.len() {
+            if let ItemKind::Submenu(menu) = &self.items[i].kind {
+                let depth = menu.depth_from(level + 1);
+                if depth > deepest {
+                    deepest = depth;
+                }
+            }
+            i += 1;
+        }
+        deepest
+    }
+}
+
+pub struct MenuItem {
+    pub label: &'static str,
+    pub kind: ItemKind,
+}
+
+pub enum ItemKind {
+    Submenu(&'static Menu),
+    /// Dilaporkan sebagai `MenuEvent::Action`
+    Action(ItemId),
+    Number {
+        id: ItemId,
+        min: i32,
+        max: i32,
+        step: i32,
+        unit: &'static str,
+    },
+    Toggle(ItemId),
+    /// Edit lewat roda karakter: putar memilih huruf, pilih menambahkannya
+    Text {
+        id: ItemId,
+        /// Jumlah karakter; dibatasi juga oleh `TEXT_MAX` byte
+        max_len: u8,
+        charset: &'static str,
+    },
+    /// Naik satu level (di root: `MenuEvent::Exit`)
+    Back,
+}
+
+/// Penyimpan nilai item; default kosong agar model cukup mengisi yang dipakai
+pub trait MenuModel {
+    fn number(&self, _id: ItemId) -> i32 {
+        0
+    }
+
+    fn set_number(&mut self, _id: ItemId, _value: i32) {}
+
+    fn toggle(&self, _id: ItemId) -> bool {
+        false
+    }
+
+    fn set_toggle(&mut self, _id: ItemId, _on: bool) {}
+
+    fn text(&self, _id: ItemId) -> &str {
+        ""
+    }
+
+    fn set_text(&mut self, _id: ItemId, _text: &str) {}
+}
+
+/// Navigasi: `Move` negatif ke atas, positif ke bawah (atau ubah nilai saat edit)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuInput {
+    Move(i32),
+    Select,
+    Back,
+}
+
+/// Peran tombol fisik untuk `MenuInput::from_button`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonRole {
+    Up,
+    Down,
+    Select,
+    Back,
+    /// Satu tombol saja: klik = turun, tekan lama = pilih, dobel-klik = kembali
+    Single,
+}
+
+impl MenuInput {
+    /// Encoder: putar = pindah (termasuk percepatan), klik = pilih, tekan lama = kembali
+    pub fn from_encoder(event: EncoderEvent) -> Option<Self> {
+        match event {
+            EncoderEvent::Turn(steps) => Some(MenuInput::Move(steps)),
+            EncoderEvent::Button(ButtonEvent::Click) => Some(MenuInput::Select),
+            EncoderEvent::Button(ButtonEvent::LongPress) => Some(MenuInput::Back),
+            EncoderEvent::Button(_) => None,
+        }
+    }
+
+    /// Atas/bawah bereaksi saat ditekan dan saat auto-repeat
+    pub fn from_button(role: ButtonRole, event: ButtonEvent) -> Option<Self> {
+        use ButtonEvent::*;
+
+        match (role, event) {
+            (ButtonRole::Up, Press | Repeat) => Some(MenuInput::Move(-1)),
+            (ButtonRole::Down, Press | Repeat) => Some(MenuInput::Move(1)),
+            (ButtonRole::Select, Click) => Some(MenuInput::Select),
+            (ButtonRole::Select, LongPress) => Some(MenuInput::Back),
+            (ButtonRole::Back, Click) => Some(MenuInput::Back),
+            (ButtonRole::Single, Click) => Some(MenuInput::Move(1)),
+            (ButtonRole::Single, LongPress) => Some(MenuInput::Select),
+            (ButtonRole::Single, DoubleClick) => Some(MenuInput::Back),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuEvent {
+    Action(ItemId),
+    /// Nilai sudah ditulis ke model
+    Changed(ItemId),
+    /// Keluar dari menu root
+    Exit,
+}
+
+#[derive(Clone, Copy)]
+struct Level {
+    menu: &'static Menu,
+    cursor: usize,
+    top: usize,
+}
+
+enum Edit {
+    Number(i32),
+    /// Teks sejauh ini dan posisi roda (charset, lalu selesai, lalu hapus)
+    Text { text: String<TEXT_MAX>, wheel: usize },
+}
+
+pub struct MenuView {
+    stack: Vec<Level, DEPTH>,
+    edit: Option<Edit>,
+}
+
+impl MenuView {
+    /// Pohon yang lebih dalam dari `DEPTH` ditolak (debug build)
+    pub fn new(root: &'static Menu) -> Self {
+        debug_assert!(root.depth() <= DEPTH, "menu lebih dalam dari DEPTH");
+
+        let mut stack = Vec::new();
+        let _ = stack.push(Level { menu: root, cursor: 0, top: 0 });
+        Self { stack, edit: None }
+    }
+
+    pub fn menu(&self) -> &'static Menu {
+        self.level().menu
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.level().cursor
+    }
+
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.edit.is_some()
+    }
+
+    /// Kembali ke root, batalkan edit
+    pub fn reset(&mut self) {
+        self.stack.truncate(1);
+        if let Some(root) = self.stack.first_mut() {
+            root.cursor = 0;
+            root.top = 0;
+        }
+        self.edit = None;
+    }
+
+    pub fn handle<M>(&mut self, input: MenuInput, model: &mut M) -> Option<MenuEvent>
+    where
+        M: MenuModel + ?Sized,
+    {
+        if self.edit.is_some() {
+            return self.handle_edit(input, model);
+        }
+
+        match input {
+            MenuInput::Move(steps) => {
+                let len = self.menu().items.len() as i32;
+                if len > 0 {
+                    let level = self.level_mut();
+                    level.cursor = (level.cursor as i32 + steps).rem_euclid(len) as usize;
+                }
+                None
+            }
+            MenuInput::Back => self.up(),
+            MenuInput::Select => {
+                let item = self.menu().items.get(self.cursor())?;
+                match item.kind {
+                    ItemKind::Submenu(menu) => {
+                        // tidak penuh: kedalaman pohon dicek di `new`
+                        let _ = self.stack.push(Level { menu, cursor: 0, top: 0 });
+                        None
+                    }
+                    ItemKind::Action(id) => Some(MenuEvent::Action(id)),
+                    ItemKind::Number { id, .. } => {
+                        self.edit = Some(Edit::Number(model.number(id)));
+                        None
+                    }
+                    ItemKind::Toggle(id) => {
+                        let on = model.toggle(id);
+                        model.set_toggle(id, !on);
+                        Some(MenuEvent::Changed(id))
+                    }
+                    ItemKind::Text { id, .. } => {
+                        // salin per karakter: nilai lama yang terlalu panjang terpotong, bukan hilang
+                        let mut text = String::new();
+                        for c in model.text(id).chars() {
+                            if text.push(c).is_err() {
+                                break;
+                            }
+                        }
+                        self.edit = Some(Edit::Text { text, wheel: 0 });
+                        None
+                    }
+                    ItemKind::Back => self.up(),
+                }
+            }
+        }
+    }
+
+    /// Judul di baris 0 (bila ada ≥ 2 baris), item di sisa baris dengan
+    /// viewport yang mengikuti kursor. Belum di-flush.
+    pub fn render<D, M>(&mut self, display: &mut D, model: &M)
+    where
+        D: TextDisplay + ?Sized,
+        M: MenuModel + ?Sized,
+    {
+        let (rows, cols) = (display.rows() as usize, display.cols() as usize);
+        let first = if rows >= 2 { 1 } else { 0 };
+        let visible = rows.saturating_sub(first).max(1);
+
+        let level = self.level_mut();
+        if level.cursor < level.top {
+            level.top = level.cursor;
+        } else if level.cursor >= level.top + visible {
+            level.top = level.cursor + 1 - visible;
+        }
+        let Level { menu, cursor, top } = *level;
+
+        if first == 1 {
+            display.write_line(0, menu.title, Align::Left);
+        }
+        for row in 0..visible {
+            let index = top + row;
+            let line = match menu.items.get(index) {
+                Some(item) => self.item_line(item, index == cursor, cols, model),
+                None => String::new(),
+            };
+            display.write_line((first + row) as u8, &line, Align::Left);
+        }
+    }
+
+    // ───────── internal ─────────
+
+    fn level(&self) -> &Level {
+        self.stack.last().expect("menu stack never empty")
+    }
+
+    fn level_mut(&mut self) -> &mut Level {
+        self.stack.last_mut().expect("menu stack never empty")
+    }
+
+    fn up(&mut self) -> Option<MenuEvent> {
+        if self.stack.len() == 1 {
+            return Some(MenuEvent::Exit);
+        }
+        self.stack.pop();
+        None
+    }
+
+    fn handle_edit<M>(&mut self, input: MenuInput, model: &mut M) -> Option<MenuEvent>
+    where
+        M: MenuModel + ?Sized,
+    {
+        let item = self.menu().items.get(self.cursor())?;
+        if input == MenuInput::Back {
+            self.edit = None;
+            return None;
+        }
+
+        match (&item.kind, self.edit.as_mut()?) {
+            (&ItemKind::Number { id, min, max, step, .. }, Edit::Number(value)) => match input {
+                MenuInput::Move(steps) => {
+                    *value = value.saturating_add(steps.saturating_mul(step)).clamp(min, max);
+                    None
+                }
+                _ => {
+                    model.set_number(id, *value);
+                    self.edit = None;
+                    Some(MenuEvent::Changed(id))
+                }
+            },
+            (&ItemKind::Text { id, max_len, charset }, Edit::Text { text, wheel }) => {
+                // roda: semua karakter charset, lalu "selesai", lalu "hapus"
+                let entries = charset.chars().count() + 2;
+                match input {
+                    MenuInput::Move(steps) => {
+                        *wheel = (*wheel as i32 + steps).rem_euclid(entries as i32) as usize;
+                        None
+                    }
+                    _ if *wheel == entries - 2 => {
+                        model.set_text(id, text);
+                        self.edit = None;
+                        Some(MenuEvent::Changed(id))
+                    }
+                    _ if *wheel == entries - 1 => {
+                        text.pop();
+                        None
+                    }
+                    _ => {
+                        // push gagal bila buffer TEXT_MAX byte penuh
+                        if text.chars().count() < max_len as usize {
+                            if let Some(c) = charset.chars().nth(*wheel) {
+                                let _ = text.push(c);
+                            }
+                        }
+                        None
+                    }
+                }
+            }
+            _ => {
+                self.edit = None;
+                None
+            }
+        }
+    }
+
+    /// Penanda kursor + label di kiri, nilai rata kanan
+    fn item_line<M>(&self, item: &MenuItem, selected: bool, cols: usize, model: &M) -> String<{ MAX_COLS * 4 }>
+    where
+        M: MenuModel + ?Sized,
+    {
+        let editing = selected.then_some(self.edit.as_ref()).flatten();
+
+        let mut value: String<{ MAX_COLS * 4 }> = String::new();
+        match (&item.kind, editing) {
+            (ItemKind::Submenu(_), _) => {
+                let _ = value.push(ARROW_RIGHT);
+            }
+            (ItemKind::Number { unit, .. }, Some(Edit::Number(v))) => {
+                let _ = write!(value, "[{}{}]", v, unit);
+            }
+            (ItemKind::Number { id, unit, .. }, _) => {
+                let _ = write!(value, "{}{}", model.number(*id), unit);
+            }
+            (ItemKind::Toggle(id), _) => {
+                let _ = value.push_str(if model.toggle(*id) { "ON" } else { "OFF" });
+            }
+            (ItemKind::Text { charset, .. }, Some(Edit::Text { text, wheel })) => {
+                let selected = match charset.chars().nth(*wheel) {
+                    Some(c) => c,
+                    None if *wheel == charset.chars().count() => ARROW_RIGHT,
+                    None => ARROW_LEFT,
+                };
+                let _ = write!(value, "{}[{}]", text, selected);
+            }
+            (ItemKind::Text { id, .. }, _) => {
+                let _ = value.push_str(model.text(*id));
+            }
+            (ItemKind::Action(_) | ItemKind::Back, _) => {}
+        }
+
+        // nilai yang terlalu panjang: tampilkan ekornya
+        let room = cols.saturating_sub(1);
+        let value_len = value.chars().count();
+        let value_str = match value.char_indices().nth(value_len.saturating_sub(room)) {
+            Some((i, _)) => &value[i..],
+            None => "",
+        };
+        let label_width = room.saturating_sub(value_str.chars().count());
+
+        let mut line = String::new();
+        let _ = line.push(if selected { '>' } else { ' ' });
+        // label dipotong agar nilai tetap terlihat; label + spasi tepat selebar sisa kolom
+        let label: String<{ MAX_COLS * 4 }> = layout::align(item.label, label_width, Align::Left).collect();
+        let _ = line.push_str(&label);
+        let _ = line.push_str(value_str);
+        line
+    }
+}
@@ -0,0 +1,114 @@
+use drivers::segment::{self, SegmentPanel};
+
+use crate::layout::Align;
+use crate::text_display::TextDisplay;
+
+/// Batas digit (8 chip MAX7219 x 8 digit)
+const MAX_DIGITS: usize = 64;
+
+/// Display 7-segment sebagai `TextDisplay` satu baris. Tulisan masuk
+/// buffer segmen; `flush` mengirim ulang semua digit bila ada perubahan.
+pub struct SegmentDisplay<P> {
+    panel: P,
+    cells: [u8; MAX_DIGITS],
+    dirty: bool,
+    // flush terakhir gagal; dicoba lagi pada flush berikutnya
+    error: bool,
+}
+
+impl<P: SegmentPanel> SegmentDisplay<P> {
+    pub fn new(panel: P) -> Self {
+        Self {
+            panel,
+            cells: [0; MAX_DIGITS],
+            dirty: true,
+            error: false,
+        }
+    }
+
+    /// Panel menjawab pada flush terakhir
+    pub fn is_online(&self) -> bool {
+        !self.error
+    }
+
+    /// Pola segmen di buffer (belum tentu sudah tampil)
+    pub fn segments(&self) -> &[u8] {
+        &self.cells[..self.digits()]
+    }
+
+    /// Akses driver, mis. untuk kecerahan
+    pub fn panel_mut(&mut self) -> &mut P {
+        &mut self.panel
+    }
+
+    pub fn into_inner(self) -> P {
+        self.panel
+    }
+
+    fn digits(&self) -> usize {
+        (self.panel.digits() as usize).min(MAX_DIGITS)
+    }
+}
+
+impl<P: SegmentPanel> TextDisplay for SegmentDisplay<P> {
+    fn cols(&self) -> u8 {
+        self.digits() as u8
+    }
+
+    fn rows(&self) -> u8 {
+        1
+    }
+
+    /// '.' menyalakan titik digit sebelumnya
+    fn write_at(&mut self, col: u8, row: u8, text: &str) {
+        let (col, digits) = (col as usize, self.digits());
+        if row != 0 || col >= digits {
+            return;
+        }
+
+        let mut encoded = [0; MAX_DIGITS];
+        let n = segment::encode_str(text, &mut encoded[..digits - col]);
+        if self.cells[col..col + n] != encoded[..n] {
+            self.cells[col..col + n].copy_from_slice(&encoded[..n]);
+            self.dirty = true;
+        }
+    }
+
+    /// Perataan dihitung per digit, jadi '.' tidak memakan kolom
+    fn write_line(&mut self, row: u8, text: &str, align: Align) {
+        let digits = self.digits();
+        if row != 0 {
+            return;
+        }
+
+        let mut encoded = [0; MAX_DIGITS];
+        let n = segment::encode_str(text, &mut encoded[..digits]);
+        let start = match align {
+            Align::Left => 0,
+            Align::Center => (digits - n) / 2,
+            Align::Right => digits - n,
+        };
+
+        let mut line = [0; MAX_DIGITS];
+        line[start..start + n].copy_from_slice(&encoded[..n]);
+        if self.cells[..digits] != line[..digits] {
+            self.cells[..digits].copy_from_slice(&line[..digits]);
+            self.dirty = true;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.cells = [0; MAX_DIGITS];
+        self.dirty = true;
+    }
+
+    fn flush(&mut self) {
+        if !self.dirty && !self.error {
+            return;
+        }
+
+        let digits = self.digits();
+        self.error = self.panel.write_segments(&self.cells[..digits]).is_err();
+        self.dirty = self.error;
+    }
+}
@@ -0,0 +1,63 @@
+use core::fmt::Write;
+
+use crate::framebuffer::MAX_COLS;
+use crate::layout::{self, Align};
+
+/// Layar teks yang dipakai logic aplikasi: LCD karakter, OLED mode teks,
+/// konsol serial, atau simulator. Tulisan boleh di-buffer sampai `flush`.
+pub trait TextDisplay {
+    fn cols(&self) -> u8;
+
+    fn rows(&self) -> u8;
+
+    /// Tulis teks mulai (col, row), dipotong di tepi kanan
+    fn write_at(&mut self, col: u8, row: u8, text: &str);
+
+    /// Kosongkan seluruh layar
+    fn clear(&mut self);
+
+    /// Tampilkan semua tulisan yang tertunda
+    fn flush(&mut self);
+
+    /// Isi satu baris penuh: teks diratakan dan sisa kolom dikosongkan
+    fn write_line(&mut self, row: u8, text: &str, align: Align) {
+        let mut line: heapless::String<{ MAX_COLS * 4 }> = heapless::String::new();
+        for c in layout::align(text, self.cols() as usize, align) {
+            if line.write_char(c).is_err() {
+                break;
+            }
+        }
+        self.write_at(0, row, &line);
+    }
+}
+
+/// Mode teks di atas panel grafis (OLED, TFT). Error bus tidak dilaporkan:
+/// baris TFT yang gagal digambar ulang saat `flush`, dan pada OLED page
+/// yang gagal tetap ditandai berubah dan dikirim lagi.
+#[cfg(feature = "graphics")]
+impl<T> TextDisplay for drivers::TextMode<T>
+where
+    T: drivers::TextTarget,
+{
+    fn cols(&self) -> u8 {
+        drivers::TextMode::cols(self)
+    }
+
+    fn rows(&self) -> u8 {
+        drivers::TextMode::rows(self)
+    }
+
+    fn write_at(&mut self, col: u8, row: u8, text: &str) {
+        let _ = drivers::TextMode::write_at(self, col, row, text);
+    }
+
+    fn clear(&mut self) {
+        let _ = drivers::TextMode::clear(self);
+    }
+
+    fn flush(&mut self) {
+        if drivers::TextMode::redraw(self).is_ok() {
+            let _ = drivers::TextMode::flush(self);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Widget LCD karakter: angka besar, bar graph, sparkline.
+//! Semua digambar ke `Region` memakai glyph CGRAM bersama (lihat `LcdDisplay::register_icon`);
+//! glyph widget yang tidak lagi tampil dibebaskan sebelum widget berikutnya digambar.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+use heapless::Vec;
+
+use crate::display::LcdDisplay;
+use crate::framebuffer::MAX_COLS;
+use crate::icons::Icon;
+
+/// Area persegi di layar, dalam sel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub col: u8,
+    pub row: u8,
+    pub width: u8,
+    pub height: u8,
+}
+
+impl Region {
+    pub const fn new(col: u8, row: u8, width: u8, height: u8) -> Self {
+        Self { col, row, width, height }
+    }
+
+    /// Potong ke ukuran panel
+    fn clip(self, cols: u8, rows: u8) -> Self {
+        let col = self.col.min(cols);
+        let row = self.row.min(rows);
+        Self {
+            col,
+            row,
+            width: self.width.min(cols - col),
+            height: self.height.min(rows - row),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
+
+// ───────── glyph ─────────
+
+const BLOCK: Icon = Icon {
+    name: "block",
+    bitmap: [0x1F; 8],
+};
+
+const BIG_UPPER: Icon = Icon {
+    name: "big_upper",
+    bitmap: [0x1F, 0x1F, 0x1F, 0, 0, 0, 0, 0],
+};
+
+const BIG_LOWER: Icon = Icon {
+    name: "big_lower",
+    bitmap: [0, 0, 0, 0, 0, 0x1F, 0x1F, 0x1F],
+};
+
+const BIG_BOTH: Icon = Icon {
+    name: "big_both",
+    bitmap: [0x1F, 0x1F, 0x1F, 0, 0, 0x1F, 0x1F, 0x1F],
+};
+
+/// Sel bar terisi 1..4 kolom dari kiri
+const BAR: [Icon; 4] = [
+    Icon { name: "bar1", bitmap: [0x10; 8] },
+    Icon { name: "bar2", bitmap: [0x18; 8] },
+    Icon { name: "bar3", bitmap: [0x1C; 8] },
+    Icon { name: "bar4", bitmap: [0x1E; 8] },
+];
+
+/// Sel sparkline terisi 1..7 baris dari bawah
+const SPARK: [Icon; 7] = [
+    Icon { name: "spark1", bitmap: spark(1) },
+    Icon { name: "spark2", bitmap: spark(2) },
+    Icon { name: "spark3", bitmap: spark(3) },
+    Icon { name: "spark4", bitmap: spark(4) },
+    Icon { name: "spark5", bitmap: spark(5) },
+    Icon { name: "spark6", bitmap: spark(6) },
+    Icon { name: "spark7", bitmap: spark(7) },
+];
+
+const fn spark(level: usize) -> [u8; 8] {
+    let mut bitmap = [0; 8];
+    let mut i = 8 - level;
+    while i < 8 {
+        bitmap[i] = 0x1F;
+        i += 1;
+    }
+    bitmap
+}
+
+/// Semua glyph milik widget (icon aplikasi tidak ikut dibebaskan)
+const WIDGET_GLYPHS: [&Icon; 15] = [
+    &BLOCK, &BIG_UPPER, &BIG_LOWER, &BIG_BOTH, &BAR[0], &BAR[1], &BAR[2], &BAR[3], &SPARK[0], &SPARK[1],
+    &SPARK[2], &SPARK[3], &SPARK[4], &SPARK[5], &SPARK[6],
+];
+
+/// Pengganti bila slot CGRAM habis
+const NO_GLYPH: u8 = b'#';
+
+// ───────── font angka besar (3 kolom x 2 baris) ─────────
+
+#[derive(Clone, Copy)]
+enum Seg {
+    Blank,
+    Block,
+    Upper,
+    Lower,
+    Both,
+}
+
+use Seg::{Blank as O, Block as F, Both as B, Lower as L, Upper as U};
+
+type BigChar = [[Seg; 3]; 2];
+
+const BIG_DIGITS: [BigChar; 10] = [
+    [[F, U, F], [F, L, F]],
+    [[U, F, O], [L, F, L]],
+    [[B, B, F], [F, L, L]],
+    [[B, B, F], [L, L, F]],
+    [[F, L, F], [O, O, F]],
+    [[F, B, B], [L, L, F]],
+    [[F, B, B], [F, L, F]],
+    [[U, U, F], [O, O, F]],
+    [[F, B, F], [F, L, F]],
+    [[F, B, F], [L, L, F]],
+];
+
+const BIG_MINUS: BigChar = [[L, L, L], [O, O, O]];
+const BIG_BLANK: BigChar = [[O, O, O], [O, O, O]];
+
+/// Lebar satu karakter besar plus jarak
+const BIG_PITCH: usize = 4;
+
+impl<I2C, D> LcdDisplay<I2C, D>
+where
+    I2C: I2c<SevenBitAddress>,
+    D: DelayNs,
+{
+    /// Angka besar 2 baris (0-9, '-', spasi), rata kanan di `region`.
+    /// `false` bila region terlalu kecil atau glyph CGRAM tidak tersedia.
+    pub fn show_big_number(&mut self, region: Region, text: &str) -> bool {
+        let region = region.clip(self.cols(), self.rows());
+        let width = (text.chars().count() * BIG_PITCH).saturating_sub(1);
+        if region.height < 2 || width > region.width as usize {
+            return false;
+        }
+
+        self.clear_region(region);
+        let Some(codes) = self.big_glyphs() else {
+            return false;
+        };
+
+        let mut lines: [Vec<u8, MAX_COLS>; 2] = Default::default();
+        for (i, c) in text.chars().enumerate() {
+            let big = match c {
+                '0'..='9' => &BIG_DIGITS[c as usize - '0' as usize],
+                '-' => &BIG_MINUS,
+                _ => &BIG_BLANK,
+            };
+            for (line, segs) in lines.iter_mut().zip(big) {
+                if i > 0 {
+                    let _ = line.push(b' ');
+                }
+                for &seg in segs {
+                    let _ = line.push(codes[seg as usize]);
+                }
+            }
+        }
+
+        let col = region.col + (region.width - width as u8);
+        for (r, line) in lines.iter().enumerate() {
+            self.put_cells(col, region.row + r as u8, line);
+        }
+        true
+    }
+
+    /// Bar graph horizontal resolusi 1/5 sel; semua baris region diisi sama
+    pub fn show_bar(&mut self, region: Region, value: u32, max: u32) -> bool {
+        let region = region.clip(self.cols(), self.rows());
+        if region.is_empty() {
+            return false;
+        }
+
+        let total = region.width as u64 * 5;
+        let pixels = match max {
+            0 => 0,
+            max => (value.min(max) as u64 * total / max as u64) as usize,
+        };
+        let (full, part) = (pixels / 5, pixels % 5);
+        self.clear_region(region);
+
+        let mut cells: Vec<u8, MAX_COLS> = Vec::new();
+        if full > 0 {
+            let Some(block) = self.register_icon(&BLOCK) else {
+                return false;
+            };
+            cells.extend(core::iter::repeat_n(block, full));
+        }
+        if part > 0 {
+            let Some(code) = self.register_icon(&BAR[part - 1]) else {
+                return false;
+            };
+            let _ = cells.push(code);
+        }
+        cells.extend(core::iter::repeat_n(b' ', region.width as usize - cells.len()));
+
+        for row in region.row..region.row + region.height {
+            self.put_cells(region.col, row, &cells);
+        }
+        true
+    }
+
+    /// Sparkline: satu kolom per nilai, nilai terbaru di kanan, `height * 8` level.
+    /// Bisa memakai ke-8 slot CGRAM; sel tanpa glyph tampil '#' dan hasilnya `false`.
+    pub fn show_sparkline(&mut self, region: Region, values: &[u16], max: u16) -> bool {
+        let region = region.clip(self.cols(), self.rows());
+        if region.is_empty() {
+            return false;
+        }
+
+        let levels = region.height as u32 * 8;
+        let values = &values[values.len().saturating_sub(region.width as usize)..];
+        let pad = region.width as usize - values.len();
+        let mut complete = true;
+        self.clear_region(region);
+
+        for r in 0..region.height {
+            // level terbawah yang jatuh di baris ini
+            let floor = (region.height - 1 - r) as u32 * 8;
+
+            let mut cells: Vec<u8, MAX_COLS> = Vec::new();
+            cells.extend(core::iter::repeat_n(b' ', pad));
+            for &v in values {
+                let level = match max {
+                    0 => 0,
+                    max => v.min(max) as u32 * levels / max as u32,
+                };
+                let icon = match level.saturating_sub(floor).min(8) {
+                    0 => {
+                        let _ = cells.push(b' ');
+                        continue;
+                    }
+                    8 => &BLOCK,
+                    fill => &SPARK[fill as usize - 1],
+                };
+
+                let code = self.register_icon(icon).unwrap_or_else(|| {
+                    complete = false;
+                    NO_GLYPH
+                });
+                let _ = cells.push(code);
+            }
+            self.put_cells(region.col, region.row + r, &cells);
+        }
+        complete
+    }
+
+    /// Bebaskan glyph widget yang tidak lagi dipakai sel mana pun di framebuffer,
+    /// mis. setelah widget ditimpa teks. Dipanggil otomatis oleh tiap widget.
+    pub fn release_widget_glyphs(&mut self) {
+        for icon in WIDGET_GLYPHS {
+            if !self.icon_in_use(icon) {
+                self.release_icon(icon);
+            }
+        }
+    }
+
+    /// Kode sel untuk tiap `Seg`
+    fn big_glyphs(&mut self) -> Option<[u8; 5]> {
+        Some([
+            b' ',
+            self.register_icon(&BLOCK)?,
+            self.register_icon(&BIG_UPPER)?,
+            self.register_icon(&BIG_LOWER)?,
+            self.register_icon(&BIG_BOTH)?,
+        ])
+    }
+
+    /// Kosongkan region lalu bebaskan glyph yang hanya dipakai isi lamanya
+    fn clear_region(&mut self, region: Region) {
+        let blank = [b' '; MAX_COLS];
+        for row in region.row..region.row + region.height {
+            self.put_cells(region.col, row, &blank[..region.width as usize]);
+        }
+        self.release_widget_glyphs();
+    }
+}
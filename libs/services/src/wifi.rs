@@ -22,6 +22,18 @@ impl<W: Wifi> WifiService<W> {
         Self { wifi, ssid: s, pass: p }
     }
 
+    pub fn wifi(&self) -> &W {
+        &self.wifi
+    }
+
+    pub fn wifi_mut(&mut self) -> &mut W {
+        &mut self.wifi
+    }
+
+    pub fn into_inner(self) -> W {
+        self.wifi
+    }
+
     pub fn start(&mut self) -> WifiStatus {
         self.wifi.connect(&self.ssid, &self.pass)
     }
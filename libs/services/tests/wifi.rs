@@ -0,0 +1,31 @@
+// ================= TEST WIFI =================
+//
+// Di integration test agar `MockWifi` (testkit) dan `WifiService`
+// memakai instance crate services yang sama.
+
+use drivers::wifi::WifiStatus;
+use services::WifiService;
+use testkit::MockWifi;
+
+#[test]
+fn wifi_start_connects_with_configured_ssid() {
+    let mut service = WifiService::new(MockWifi::default(), "OfficeWiFi", "12345678");
+
+    assert_eq!(service.start(), WifiStatus::Connected);
+
+    let wifi = service.into_inner();
+    assert_eq!(wifi.last_ssid.as_deref(), Some("OfficeWiFi"));
+}
+
+#[test]
+fn wifi_poll_reconnects_after_link_drop() {
+    let mut service = WifiService::new(MockWifi::script(&[true, false, true]), "MySSID", "secret");
+
+    assert_eq!(service.start(), WifiStatus::Connected);
+    assert_eq!(service.poll(), WifiStatus::Connected);
+
+    service.wifi_mut().drop_link();
+    assert_eq!(service.poll(), WifiStatus::Disconnected);
+    assert_eq!(service.poll(), WifiStatus::Connected);
+    assert_eq!(service.wifi().connects, 3);
+}
@@ -0,0 +1,45 @@
+use core::cell::Cell;
+use std::rc::Rc;
+
+use embedded_hal::delay::DelayNs;
+
+/// Delay virtual: tidak tidur, hanya menjumlahkan waktu yang diminta
+#[derive(Clone, Default)]
+pub struct MockDelay {
+    elapsed: Rc<Cell<u64>>,
+}
+
+impl MockDelay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn elapsed_ns(&self) -> u64 {
+        self.elapsed.get()
+    }
+
+    pub fn elapsed_us(&self) -> u64 {
+        self.elapsed.get() / 1_000
+    }
+
+    pub fn elapsed_ms(&self) -> u64 {
+        self.elapsed.get() / 1_000_000
+    }
+
+    pub fn reset(&self) {
+        self.elapsed.set(0);
+    }
+}
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.elapsed.set(self.elapsed.get() + ns as u64);
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for MockDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        DelayNs::delay_ns(self, ns)
+    }
+}
@@ -0,0 +1,124 @@
+use core::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::digital::{ErrorKind, ErrorType, InputPin, OutputPin, StatefulOutputPin};
+
+/// Error pin dari mock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PinError;
+
+impl embedded_hal::digital::Error for PinError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+#[derive(Default)]
+struct State {
+    level: bool,
+    history: Vec<bool>,
+    inputs: VecDeque<bool>,
+    fail: u32,
+}
+
+/// Mock GPIO (output dan input); clone berbagi pin yang sama
+#[derive(Clone, Default)]
+pub struct MockPin {
+    state: Rc<RefCell<State>>,
+}
+
+impl MockPin {
+    pub fn new(level: bool) -> Self {
+        let pin = Self::default();
+        pin.state.borrow_mut().level = level;
+        pin
+    }
+
+    pub fn level(&self) -> bool {
+        self.state.borrow().level
+    }
+
+    /// Level dari luar (tombol, sinyal) untuk dibaca sebagai input
+    pub fn set_level(&self, level: bool) {
+        self.state.borrow_mut().level = level;
+    }
+
+    /// Urutan level untuk pembacaan input berikutnya, satu per baca
+    pub fn push_levels(&self, levels: &[bool]) {
+        self.state.borrow_mut().inputs.extend(levels.iter().copied());
+    }
+
+    /// Semua level yang pernah di-drive sebagai output
+    pub fn history(&self) -> Vec<bool> {
+        self.state.borrow().history.clone()
+    }
+
+    /// `n` operasi berikutnya gagal
+    pub fn fail_next(&self, n: u32) {
+        self.state.borrow_mut().fail = n;
+    }
+
+    fn check(&self) -> Result<(), PinError> {
+        let mut state = self.state.borrow_mut();
+        if state.fail > 0 {
+            state.fail -= 1;
+            return Err(PinError);
+        }
+        Ok(())
+    }
+
+    fn drive(&mut self, level: bool) -> Result<(), PinError> {
+        self.check()?;
+        let mut state = self.state.borrow_mut();
+        state.level = level;
+        state.history.push(level);
+        Ok(())
+    }
+
+    fn sample(&mut self) -> Result<bool, PinError> {
+        self.check()?;
+        let mut state = self.state.borrow_mut();
+        if let Some(level) = state.inputs.pop_front() {
+            state.level = level;
+        }
+        Ok(state.level)
+    }
+}
+
+impl ErrorType for MockPin {
+    type Error = PinError;
+}
+
+impl OutputPin for MockPin {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.drive(true)
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.drive(false)
+    }
+}
+
+impl StatefulOutputPin for MockPin {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        self.check()?;
+        Ok(self.level())
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        self.check()?;
+        Ok(!self.level())
+    }
+}
+
+impl InputPin for MockPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.sample()
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.sample().map(|level| !level)
+    }
+}
@@ -0,0 +1,401 @@
+//! Emulator HD44780 di belakang backpack PCF8574
+//!
+//! Byte yang dikirim lewat I2C didecode persis seperti chip aslinya:
+//! D7..D4 di nibble atas, RS/RW/EN/backlight di nibble bawah, data
+//! di-latch pada falling edge EN.
+
+use core::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::string::String;
+use std::vec::Vec;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation, SevenBitAddress};
+
+use crate::i2c::MockError;
+
+// Bit PCF8574 (wiring backpack umum)
+const RS: u8 = 1 << 0;
+const RW: u8 = 1 << 1;
+const EN: u8 = 1 << 2;
+const BL: u8 = 1 << 3;
+
+const DDRAM_SIZE: usize = 0x80;
+const CGRAM_SIZE: usize = 0x40;
+
+/// State controller HD44780
+pub struct Hd44780 {
+    cols: usize,
+    rows: usize,
+
+    ddram: [u8; DDRAM_SIZE],
+    cgram: [u8; CGRAM_SIZE],
+    addr: u8,
+    in_cgram: bool,
+
+    four_bit: bool,
+    two_line: bool,
+    increment: bool,
+    shift_on_write: bool,
+    shift: i32,
+
+    display_on: bool,
+    cursor_on: bool,
+    blink_on: bool,
+    backlight: bool,
+
+    pending: Option<u8>,
+    read_low: bool,
+    last: u8,
+}
+
+impl Hd44780 {
+    pub fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            ddram: [b' '; DDRAM_SIZE],
+            cgram: [0; CGRAM_SIZE],
+            addr: 0,
+            in_cgram: false,
+            four_bit: false,
+            two_line: false,
+            increment: true,
+            shift_on_write: false,
+            shift: 0,
+            display_on: false,
+            cursor_on: false,
+            blink_on: false,
+            backlight: false,
+            pending: None,
+            read_low: false,
+            last: 0,
+        }
+    }
+
+    // ───────── inspeksi ─────────
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Kode karakter mentah yang terlihat di satu baris
+    pub fn cells(&self, row: usize) -> Vec<u8> {
+        (0..self.cols)
+            .map(|col| self.ddram[self.visible_addr(col, row) as usize])
+            .collect()
+    }
+
+    /// Teks satu baris; kode di luar ASCII tampil sebagai '?'
+    pub fn row(&self, row: usize) -> String {
+        self.cells(row)
+            .into_iter()
+            .map(|b| if (0x20..0x7F).contains(&b) { b as char } else { '?' })
+            .collect()
+    }
+
+    /// Bitmap CGRAM yang tampil di (col, row); `None` untuk karakter ROM
+    pub fn cell_glyph(&self, col: usize, row: usize) -> Option<[u8; 8]> {
+        let code = *self.cells(row).get(col)?;
+        (code < 0x10).then(|| self.glyph(code as usize & 7))
+    }
+
+    /// Semua baris, urut dari atas
+    pub fn lines(&self) -> Vec<String> {
+        (0..self.rows).map(|r| self.row(r)).collect()
+    }
+
+    /// Posisi kursor (col, row) bila alamat DDRAM sedang terlihat
+    pub fn cursor(&self) -> Option<(usize, usize)> {
+        if self.in_cgram {
+            return None;
+        }
+        (0..self.rows)
+            .flat_map(|row| (0..self.cols).map(move |col| (col, row)))
+            .find(|&(col, row)| self.visible_addr(col, row) == self.addr)
+    }
+
+    pub fn address(&self) -> u8 {
+        self.addr
+    }
+
+    /// Bitmap 8 baris glyph CGRAM `slot` (0..8)
+    pub fn glyph(&self, slot: usize) -> [u8; 8] {
+        let mut out = [0; 8];
+        out.copy_from_slice(&self.cgram[slot * 8..slot * 8 + 8]);
+        out
+    }
+
+    pub fn four_bit(&self) -> bool {
+        self.four_bit
+    }
+
+    pub fn two_line(&self) -> bool {
+        self.two_line
+    }
+
+    pub fn display_on(&self) -> bool {
+        self.display_on
+    }
+
+    pub fn cursor_on(&self) -> bool {
+        self.cursor_on
+    }
+
+    pub fn blink_on(&self) -> bool {
+        self.blink_on
+    }
+
+    pub fn backlight(&self) -> bool {
+        self.backlight
+    }
+
+    pub fn increment(&self) -> bool {
+        self.increment
+    }
+
+    /// Jumlah shift display (positif = isi bergeser ke kiri)
+    pub fn shift(&self) -> i32 {
+        self.shift
+    }
+
+    // ───────── bus PCF8574 ─────────
+
+    /// Satu byte yang ditulis ke port PCF8574
+    pub fn write_port(&mut self, byte: u8) {
+        self.backlight = byte & BL != 0;
+
+        let falling = self.last & EN != 0 && byte & EN == 0;
+        if falling {
+            let prev = self.last;
+            if prev & RW == 0 {
+                self.latch(prev >> 4, prev & RS != 0);
+            } else {
+                self.read_done(prev & RS != 0);
+            }
+        }
+        self.last = byte;
+    }
+
+    /// Nilai port saat dibaca; D7..D4 dikemudikan controller bila RW=1 dan EN=1
+    pub fn read_port(&self) -> u8 {
+        if self.last & RW == 0 || self.last & EN == 0 {
+            return self.last;
+        }
+
+        let value = if self.last & RS != 0 {
+            self.read_data()
+        } else {
+            self.addr & 0x7F
+        };
+        let nibble = if self.four_bit && self.read_low { value & 0x0F } else { value >> 4 };
+
+        (nibble << 4) | (self.last & 0x0F)
+    }
+
+    // ───────── controller ─────────
+
+    fn latch(&mut self, nibble: u8, rs: bool) {
+        if !self.four_bit {
+            // mode 8-bit: D3..D0 tidak tersambung, dibaca 0
+            self.execute(nibble << 4, rs);
+            return;
+        }
+
+        match self.pending.take() {
+            None => self.pending = Some(nibble),
+            Some(high) => self.execute((high << 4) | nibble, rs),
+        }
+    }
+
+    fn read_done(&mut self, rs: bool) {
+        if self.four_bit && !self.read_low {
+            self.read_low = true;
+            return;
+        }
+        self.read_low = false;
+        if rs {
+            self.advance();
+        }
+    }
+
+    fn read_data(&self) -> u8 {
+        if self.in_cgram {
+            self.cgram[(self.addr as usize) % CGRAM_SIZE]
+        } else {
+            self.ddram[self.addr as usize]
+        }
+    }
+
+    fn execute(&mut self, val: u8, rs: bool) {
+        if rs {
+            self.write_data(val);
+            return;
+        }
+
+        if val & 0x80 != 0 {
+            self.addr = val & 0x7F;
+            self.in_cgram = false;
+        } else if val & 0x40 != 0 {
+            self.addr = val & 0x3F;
+            self.in_cgram = true;
+        } else if val & 0x20 != 0 {
+            self.four_bit = val & 0x10 == 0;
+            self.two_line = val & 0x08 != 0;
+            self.pending = None;
+        } else if val & 0x10 != 0 {
+            let right = val & 0x04 != 0;
+            if val & 0x08 != 0 {
+                self.shift += if right { -1 } else { 1 };
+            } else {
+                self.step(right);
+            }
+        } else if val & 0x08 != 0 {
+            self.display_on = val & 0x04 != 0;
+            self.cursor_on = val & 0x02 != 0;
+            self.blink_on = val & 0x01 != 0;
+        } else if val & 0x04 != 0 {
+            self.increment = val & 0x02 != 0;
+            self.shift_on_write = val & 0x01 != 0;
+        } else if val & 0x02 != 0 {
+            self.addr = 0;
+            self.in_cgram = false;
+            self.shift = 0;
+        } else if val == 0x01 {
+            self.ddram = [b' '; DDRAM_SIZE];
+            self.addr = 0;
+            self.in_cgram = false;
+            self.shift = 0;
+            self.increment = true;
+        }
+    }
+
+    fn write_data(&mut self, val: u8) {
+        if self.in_cgram {
+            self.cgram[(self.addr as usize) % CGRAM_SIZE] = val & 0x1F;
+        } else {
+            self.ddram[self.addr as usize] = val;
+            if self.shift_on_write {
+                self.shift += if self.increment { 1 } else { -1 };
+            }
+        }
+        self.advance();
+    }
+
+    fn advance(&mut self) {
+        self.step(self.increment);
+    }
+
+    fn step(&mut self, forward: bool) {
+        if self.in_cgram {
+            self.addr = if forward { (self.addr + 1) & 0x3F } else { self.addr.wrapping_sub(1) & 0x3F };
+            return;
+        }
+
+        self.addr = if self.two_line {
+            match (self.addr, forward) {
+                (0x27, true) => 0x40,
+                (0x67, true) => 0x00,
+                (0x00, false) => 0x67,
+                (0x40, false) => 0x27,
+                (a, true) => (a + 1) & 0x7F,
+                (a, false) => a - 1,
+            }
+        } else {
+            match (self.addr, forward) {
+                (0x4F, true) => 0x00,
+                (0x00, false) => 0x4F,
+                (a, true) => a + 1,
+                (a, false) => a - 1,
+            }
+        };
+    }
+
+    /// Alamat DDRAM yang tampil di (col, row), termasuk efek shift display
+    fn visible_addr(&self, col: usize, row: usize) -> u8 {
+        let line_len: i32 = if self.two_line { 40 } else { 80 };
+        let (base, start) = if self.two_line {
+            ((row % 2) as i32 * 0x40, (row / 2 * self.cols) as i32)
+        } else {
+            (0, (row * self.cols) as i32)
+        };
+        let offset = (start + col as i32 + self.shift).rem_euclid(line_len);
+        (base + offset) as u8
+    }
+}
+
+/// LCD virtual di alamat I2C tertentu; clone berbagi layar yang sama
+#[derive(Clone)]
+pub struct Pcf8574Lcd {
+    addr: SevenBitAddress,
+    lcd: Rc<RefCell<Hd44780>>,
+    present: Rc<Cell<bool>>,
+}
+
+impl Pcf8574Lcd {
+    pub fn new(addr: SevenBitAddress, cols: usize, rows: usize) -> Self {
+        Self {
+            addr,
+            lcd: Rc::new(RefCell::new(Hd44780::new(cols, rows))),
+            present: Rc::new(Cell::new(true)),
+        }
+    }
+
+    /// Cabut/pasang modul. Saat dicabut semua transaksi NACK;
+    /// saat dipasang lagi controller kembali ke state power-on.
+    pub fn set_present(&self, present: bool) {
+        if present && !self.present.get() {
+            let mut lcd = self.lcd.borrow_mut();
+            *lcd = Hd44780::new(lcd.cols(), lcd.rows());
+        }
+        self.present.set(present);
+    }
+
+    /// Akses ke state controller (untuk assert di test)
+    pub fn screen(&self) -> core::cell::Ref<'_, Hd44780> {
+        self.lcd.borrow()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.lcd.borrow().lines()
+    }
+}
+
+impl ErrorType for Pcf8574Lcd {
+    type Error = MockError;
+}
+
+impl I2c<SevenBitAddress> for Pcf8574Lcd {
+    fn transaction(
+        &mut self,
+        addr: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if addr != self.addr || !self.present.get() {
+            return Err(MockError(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)));
+        }
+
+        let mut lcd = self.lcd.borrow_mut();
+        for op in operations {
+            match op {
+                Operation::Write(bytes) => bytes.iter().for_each(|&b| lcd.write_port(b)),
+                Operation::Read(buf) => buf.fill(lcd.read_port()),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c<SevenBitAddress> for Pcf8574Lcd {
+    async fn transaction(
+        &mut self,
+        addr: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2c::transaction(self, addr, operations)
+    }
+}
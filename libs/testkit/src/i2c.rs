@@ -0,0 +1,146 @@
+use core::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation, SevenBitAddress};
+
+/// Error dari mock, membawa `ErrorKind` yang diinjeksikan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockError(pub ErrorKind);
+
+impl embedded_hal::i2c::Error for MockError {
+    fn kind(&self) -> ErrorKind {
+        self.0
+    }
+}
+
+/// Satu operasi I2C yang tercatat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum I2cOp {
+    Write(SevenBitAddress, Vec<u8>),
+    Read(SevenBitAddress, usize),
+}
+
+#[derive(Default)]
+struct State {
+    ops: Vec<I2cOp>,
+    reads: VecDeque<Vec<u8>>,
+    // None = operasi dilewatkan tanpa error (lihat `fail_after`)
+    errors: VecDeque<Option<ErrorKind>>,
+    absent: bool,
+}
+
+/// Mock I2C yang mencatat semua operasi; clone berbagi catatan yang sama
+#[derive(Clone, Default)]
+pub struct MockI2c {
+    state: Rc<RefCell<State>>,
+}
+
+impl MockI2c {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Jumlah operasi tulis sejauh ini
+    pub fn count(&self) -> u32 {
+        self.state
+            .borrow()
+            .ops
+            .iter()
+            .filter(|op| matches!(op, I2cOp::Write(..)))
+            .count() as u32
+    }
+
+    pub fn ops(&self) -> Vec<I2cOp> {
+        self.state.borrow().ops.clone()
+    }
+
+    /// Semua byte yang ditulis, berurutan
+    pub fn written(&self) -> Vec<u8> {
+        self.state
+            .borrow()
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                I2cOp::Write(_, bytes) => Some(bytes.as_slice()),
+                I2cOp::Read(..) => None,
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.state.borrow_mut().ops.clear();
+    }
+
+    /// Jawaban untuk operasi baca berikutnya (FIFO); bila kosong dibaca 0
+    pub fn push_read(&self, bytes: &[u8]) {
+        self.state.borrow_mut().reads.push_back(bytes.to_vec());
+    }
+
+    /// Operasi berikutnya gagal dengan `kind`
+    pub fn fail_next(&self, kind: ErrorKind) {
+        self.state.borrow_mut().errors.push_back(Some(kind));
+    }
+
+    /// `ops` operasi berikutnya berhasil, lalu satu operasi gagal dengan `kind`
+    pub fn fail_after(&self, ops: usize, kind: ErrorKind) {
+        let mut state = self.state.borrow_mut();
+        state.errors.extend(core::iter::repeat_n(None, ops));
+        state.errors.push_back(Some(kind));
+    }
+
+    /// Perangkat dicabut: semua operasi NACK sampai dipasang lagi
+    pub fn set_absent(&self, absent: bool) {
+        self.state.borrow_mut().absent = absent;
+    }
+}
+
+impl ErrorType for MockI2c {
+    type Error = MockError;
+}
+
+impl I2c<SevenBitAddress> for MockI2c {
+    fn transaction(
+        &mut self,
+        addr: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut state = self.state.borrow_mut();
+
+        if state.absent {
+            return Err(MockError(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)));
+        }
+
+        for op in operations {
+            if let Some(kind) = state.errors.pop_front().flatten() {
+                return Err(MockError(kind));
+            }
+
+            match op {
+                Operation::Write(bytes) => state.ops.push(I2cOp::Write(addr, bytes.to_vec())),
+                Operation::Read(buf) => {
+                    let data = state.reads.pop_front().unwrap_or_default();
+                    for (dst, src) in buf.iter_mut().zip(data.iter().chain(core::iter::repeat(&0))) {
+                        *dst = *src;
+                    }
+                    state.ops.push(I2cOp::Read(addr, buf.len()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c<SevenBitAddress> for MockI2c {
+    async fn transaction(
+        &mut self,
+        addr: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2c::transaction(self, addr, operations)
+    }
+}
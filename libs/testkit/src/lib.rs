@@ -0,0 +1,138 @@
+//! Perlengkapan test bersama untuk crate-crate di workspace
+
+pub mod delay;
+pub mod digital;
+pub mod hd44780;
+pub mod i2c;
+pub mod oled;
+pub mod pwm;
+pub mod spi;
+pub mod tm1637;
+#[cfg(feature = "wifi")]
+pub mod wifi;
+
+pub use delay::MockDelay;
+pub use digital::MockPin;
+pub use hd44780::{Hd44780, Pcf8574Lcd};
+pub use i2c::{I2cOp, MockI2c};
+pub use oled::{OledBus, OledPanel};
+pub use pwm::MockPwm;
+pub use spi::{DcFrame, MockSpi};
+pub use tm1637::{Tm1637Chip, Tm1637Pin};
+#[cfg(feature = "wifi")]
+pub use wifi::MockWifi;
+
+// ================= UNIT TESTS =================
+
+#[cfg(test)]
+mod tests {
+    use super::{Hd44780, MockDelay, MockI2c, MockPin, MockSpi};
+
+    use embedded_hal::delay::DelayNs;
+    use embedded_hal::digital::InputPin;
+    use embedded_hal::i2c::{ErrorKind, I2c};
+    use embedded_hal::spi::SpiDevice;
+
+    const EN: u8 = 1 << 2;
+    const RS: u8 = 1 << 0;
+
+    fn nibble(lcd: &mut Hd44780, n: u8, flags: u8) {
+        lcd.write_port((n << 4) | flags | EN);
+        lcd.write_port((n << 4) | flags);
+    }
+
+    fn byte(lcd: &mut Hd44780, b: u8, flags: u8) {
+        nibble(lcd, b >> 4, flags);
+        nibble(lcd, b & 0x0F, flags);
+    }
+
+    fn init(lcd: &mut Hd44780) {
+        for n in [0x03, 0x03, 0x03, 0x02] {
+            nibble(lcd, n, 0);
+        }
+        byte(lcd, 0x28, 0);
+        byte(lcd, 0x0C, 0);
+        byte(lcd, 0x01, 0);
+    }
+
+    #[test]
+    fn init_sequence_enters_four_bit_two_line() {
+        let mut lcd = Hd44780::new(16, 2);
+        init(&mut lcd);
+
+        assert!(lcd.four_bit());
+        assert!(lcd.two_line());
+        assert!(lcd.display_on());
+        assert_eq!(lcd.cursor(), Some((0, 0)));
+    }
+
+    #[test]
+    fn data_lands_at_ddram_address() {
+        let mut lcd = Hd44780::new(20, 4);
+        init(&mut lcd);
+
+        byte(&mut lcd, 0x80 | 0x54, 0);
+        byte(&mut lcd, b'A', RS);
+        byte(&mut lcd, b'B', RS);
+
+        assert_eq!(lcd.row(3), "AB                  ");
+        assert_eq!(lcd.cursor(), Some((2, 3)));
+    }
+
+    #[test]
+    fn data_without_enable_edge_is_ignored() {
+        let mut lcd = Hd44780::new(16, 2);
+        init(&mut lcd);
+
+        lcd.write_port((b'X' & 0xF0) | RS);
+        lcd.write_port(((b'X' & 0x0F) << 4) | RS);
+
+        assert_eq!(lcd.row(0), " ".repeat(16));
+    }
+
+    // ===== MOCKS =====
+
+    #[test]
+    fn mock_i2c_scripts_reads_and_injects_errors() {
+        let mut i2c = MockI2c::new();
+        i2c.push_read(&[0xAB]);
+        i2c.fail_next(ErrorKind::Bus);
+
+        assert_eq!(i2c.write(0x27, &[1]).unwrap_err().0, ErrorKind::Bus);
+
+        let mut buf = [0; 2];
+        i2c.write_read(0x27, &[2], &mut buf).unwrap();
+
+        assert_eq!(buf, [0xAB, 0]);
+        assert_eq!(i2c.written(), [2]);
+        assert_eq!(i2c.count(), 1);
+    }
+
+    #[test]
+    fn mock_delay_accounts_virtual_time() {
+        let mut delay = MockDelay::new();
+        delay.delay_ms(2);
+        delay.delay_us(500);
+
+        assert_eq!(delay.elapsed_us(), 2_500);
+    }
+
+    #[test]
+    fn mock_pin_replays_input_levels() {
+        let mut pin = MockPin::new(false);
+        pin.push_levels(&[true, false]);
+
+        assert!(pin.is_high().unwrap());
+        assert!(pin.is_low().unwrap());
+        assert!(pin.is_low().unwrap());
+    }
+
+    #[test]
+    fn mock_spi_records_per_transaction() {
+        let mut spi = MockSpi::new();
+        spi.write(&[0x2A]).unwrap();
+        spi.write(&[0x00, 0x10]).unwrap();
+
+        assert_eq!(spi.transactions(), [vec![0x2A], vec![0x00, 0x10]]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Emulator panel OLED SSD1306 / SH1106 di bus I2C
+//!
+//! Byte kontrol (Co, D/C) dan perintah page addressing didecode ke GDDRAM,
+//! jadi isi panel bisa di-snapshot sebagai ASCII-art atau gambar PBM.
+
+use core::cell::RefCell;
+use std::rc::Rc;
+use std::string::String;
+use std::vec::Vec;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation, SevenBitAddress};
+
+use crate::i2c::MockError;
+
+const RAM_COLS: usize = 132;
+const PAGES: usize = 8;
+
+/// State controller: GDDRAM dan register yang relevan untuk test
+pub struct OledPanel {
+    width: usize,
+    height: usize,
+    // kolom RAM pertama yang tampil (SH1106: 2)
+    offset: usize,
+
+    ram: [[u8; RAM_COLS]; PAGES],
+    page: usize,
+    column: usize,
+
+    // perintah yang masih menunggu argumen
+    pending: Option<(u8, usize)>,
+
+    display_on: bool,
+    inverted: bool,
+    contrast: u8,
+    data_bytes: usize,
+}
+
+impl OledPanel {
+    fn new(width: usize, height: usize, offset: usize) -> Self {
+        Self {
+            width,
+            height,
+            offset,
+            ram: [[0; RAM_COLS]; PAGES],
+            page: 0,
+            column: 0,
+            pending: None,
+            display_on: false,
+            inverted: false,
+            contrast: 0x7F,
+            data_bytes: 0,
+        }
+    }
+
+    // ───────── inspeksi ─────────
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Isi GDDRAM di (x, y) yang tampil (belum termasuk efek invert)
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.ram[y / 8][x + self.offset] & (1 << (y % 8)) != 0
+    }
+
+    /// ASCII-art satu area: '#' pixel nyala, '.' mati
+    pub fn snapshot(&self, x: usize, y: usize, w: usize, h: usize) -> Vec<String> {
+        (y..y + h)
+            .map(|py| (x..x + w).map(|px| if self.pixel(px, py) { '#' } else { '.' }).collect())
+            .collect()
+    }
+
+    /// Gambar PBM (P1) seluruh panel, bisa disimpan dan dibuka di image viewer
+    pub fn to_pbm(&self) -> String {
+        let mut out = std::format!("P1\n{} {}\n", self.width, self.height);
+        for y in 0..self.height {
+            let row: Vec<&str> = (0..self.width).map(|x| if self.pixel(x, y) { "1" } else { "0" }).collect();
+            out.push_str(&row.join(" "));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn display_on(&self) -> bool {
+        self.display_on
+    }
+
+    pub fn inverted(&self) -> bool {
+        self.inverted
+    }
+
+    pub fn contrast(&self) -> u8 {
+        self.contrast
+    }
+
+    /// Jumlah byte data GDDRAM yang diterima sejak dibuat / `reset_stats`
+    pub fn data_bytes(&self) -> usize {
+        self.data_bytes
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.data_bytes = 0;
+    }
+
+    // ───────── bus ─────────
+
+    /// Satu transaksi tulis: byte kontrol lalu perintah/data
+    pub fn write(&mut self, bytes: &[u8]) {
+        let mut i = 0;
+        while i < bytes.len() {
+            let control = bytes[i];
+            let continuation = control & 0x80 != 0;
+            let data = control & 0x40 != 0;
+            i += 1;
+
+            // Co=1: hanya satu byte lalu byte kontrol lagi
+            let end = if continuation { (i + 1).min(bytes.len()) } else { bytes.len() };
+            for &b in &bytes[i..end] {
+                if data {
+                    self.write_data(b);
+                } else {
+                    self.command(b);
+                }
+            }
+            i = end;
+        }
+    }
+
+    fn write_data(&mut self, b: u8) {
+        if self.column < RAM_COLS {
+            self.ram[self.page][self.column] = b;
+        }
+        self.column = (self.column + 1).min(RAM_COLS);
+        self.data_bytes += 1;
+    }
+
+    fn command(&mut self, b: u8) {
+        if let Some((cmd, left)) = self.pending.take() {
+            if cmd == 0x81 {
+                self.contrast = b;
+            }
+            if left > 1 {
+                self.pending = Some((cmd, left - 1));
+            }
+            return;
+        }
+
+        match b {
+            0x00..=0x0F => self.column = (self.column & 0xF0) | b as usize,
+            0x10..=0x1F => self.column = (self.column & 0x0F) | ((b as usize & 0x0F) << 4),
+            0xAE | 0xAF => self.display_on = b == 0xAF,
+            0xA6 | 0xA7 => self.inverted = b == 0xA7,
+            0xB0..=0xB7 => self.page = (b & 0x07) as usize,
+            0x81 | 0x8D | 0xA8 | 0xAD | 0xD3 | 0xD5 | 0xD9 | 0xDA | 0xDB | 0x20 => self.pending = Some((b, 1)),
+            0x21 | 0x22 => self.pending = Some((b, 2)),
+            _ => {}
+        }
+    }
+}
+
+/// Modul OLED virtual di alamat I2C tertentu; clone berbagi panel yang sama
+#[derive(Clone)]
+pub struct OledBus {
+    addr: SevenBitAddress,
+    panel: Rc<RefCell<OledPanel>>,
+}
+
+impl OledBus {
+    pub fn ssd1306(addr: SevenBitAddress, height: usize) -> Self {
+        Self::new(addr, height, 0)
+    }
+
+    pub fn sh1106(addr: SevenBitAddress, height: usize) -> Self {
+        Self::new(addr, height, 2)
+    }
+
+    fn new(addr: SevenBitAddress, height: usize, offset: usize) -> Self {
+        Self {
+            addr,
+            panel: Rc::new(RefCell::new(OledPanel::new(128, height, offset))),
+        }
+    }
+
+    pub fn panel(&self) -> core::cell::Ref<'_, OledPanel> {
+        self.panel.borrow()
+    }
+
+    pub fn panel_mut(&self) -> core::cell::RefMut<'_, OledPanel> {
+        self.panel.borrow_mut()
+    }
+}
+
+impl ErrorType for OledBus {
+    type Error = MockError;
+}
+
+impl I2c<SevenBitAddress> for OledBus {
+    fn transaction(
+        &mut self,
+        addr: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if addr != self.addr {
+            return Err(MockError(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)));
+        }
+
+        let mut panel = self.panel.borrow_mut();
+        for op in operations {
+            match op {
+                Operation::Write(bytes) => panel.write(bytes),
+                // SSD1306 lewat I2C tidak bisa dibaca
+                Operation::Read(buf) => buf.fill(0),
+            }
+        }
+        Ok(())
+    }
+}
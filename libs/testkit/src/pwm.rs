@@ -0,0 +1,53 @@
+use core::cell::RefCell;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::pwm::{ErrorType, SetDutyCycle};
+
+#[derive(Default)]
+struct State {
+    duty: u16,
+    history: Vec<u16>,
+}
+
+/// Mock kanal PWM; clone berbagi kanal yang sama
+#[derive(Clone)]
+pub struct MockPwm {
+    max_duty: u16,
+    state: Rc<RefCell<State>>,
+}
+
+impl MockPwm {
+    pub fn new(max_duty: u16) -> Self {
+        Self {
+            max_duty,
+            state: Rc::default(),
+        }
+    }
+
+    pub fn duty(&self) -> u16 {
+        self.state.borrow().duty
+    }
+
+    /// Semua duty cycle yang pernah di-set
+    pub fn history(&self) -> Vec<u16> {
+        self.state.borrow().history.clone()
+    }
+}
+
+impl ErrorType for MockPwm {
+    type Error = core::convert::Infallible;
+}
+
+impl SetDutyCycle for MockPwm {
+    fn max_duty_cycle(&self) -> u16 {
+        self.max_duty
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        let mut state = self.state.borrow_mut();
+        state.duty = duty;
+        state.history.push(duty);
+        Ok(())
+    }
+}
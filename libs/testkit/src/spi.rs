@@ -0,0 +1,178 @@
+use core::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiBus, SpiDevice};
+
+use crate::digital::MockPin;
+
+/// Error SPI dari mock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpiError(pub ErrorKind);
+
+impl embedded_hal::spi::Error for SpiError {
+    fn kind(&self) -> ErrorKind {
+        self.0
+    }
+}
+
+/// Satu transaksi SPI menurut level pin D/C saat chip-select aktif
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DcFrame {
+    Command(Vec<u8>),
+    Data(Vec<u8>),
+}
+
+#[derive(Default)]
+struct State {
+    transactions: Vec<Vec<u8>>,
+    // level D/C di awal tiap transaksi (bila pin dipasang)
+    dc_levels: Vec<bool>,
+    dc: Option<MockPin>,
+    reads: VecDeque<u8>,
+    errors: VecDeque<ErrorKind>,
+    delay_ns: u64,
+}
+
+/// Mock SPI (device dan bus) yang mencatat byte per transaksi
+#[derive(Clone, Default)]
+pub struct MockSpi {
+    state: Rc<RefCell<State>>,
+}
+
+impl MockSpi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Byte yang ditulis, satu `Vec` per transaksi (chip-select)
+    pub fn transactions(&self) -> Vec<Vec<u8>> {
+        self.state.borrow().transactions.clone()
+    }
+
+    /// Semua byte yang ditulis, berurutan
+    pub fn written(&self) -> Vec<u8> {
+        self.state.borrow().transactions.concat()
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.borrow_mut();
+        state.transactions.clear();
+        state.dc_levels.clear();
+    }
+
+    /// Pasang pin D/C (low = command) agar transaksi bisa dipisah lewat `dc_frames`
+    pub fn attach_dc(&self, pin: &MockPin) {
+        self.state.borrow_mut().dc = Some(pin.clone());
+    }
+
+    /// Transaksi `SpiDevice` beserta level D/C-nya; kosong bila pin belum dipasang
+    pub fn dc_frames(&self) -> Vec<DcFrame> {
+        let state = self.state.borrow();
+        state
+            .dc_levels
+            .iter()
+            .zip(&state.transactions)
+            .map(|(&data, bytes)| if data { DcFrame::Data(bytes.clone()) } else { DcFrame::Command(bytes.clone()) })
+            .collect()
+    }
+
+    /// Byte yang akan diterima pada pembacaan berikutnya; bila habis dibaca 0
+    pub fn push_read(&self, bytes: &[u8]) {
+        self.state.borrow_mut().reads.extend(bytes.iter().copied());
+    }
+
+    pub fn fail_next(&self, kind: ErrorKind) {
+        self.state.borrow_mut().errors.push_back(kind);
+    }
+
+    /// Total delay yang diminta lewat `Operation::DelayNs`
+    pub fn delay_ns(&self) -> u64 {
+        self.state.borrow().delay_ns
+    }
+
+    fn begin(&mut self) -> Result<(), SpiError> {
+        let mut state = self.state.borrow_mut();
+        if let Some(kind) = state.errors.pop_front() {
+            return Err(SpiError(kind));
+        }
+        state.transactions.push(Vec::new());
+        if let Some(level) = state.dc.as_ref().map(MockPin::level) {
+            state.dc_levels.push(level);
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, bytes: &[u8]) {
+        let mut state = self.state.borrow_mut();
+        if state.transactions.is_empty() {
+            state.transactions.push(Vec::new());
+        }
+        if let Some(last) = state.transactions.last_mut() {
+            last.extend_from_slice(bytes);
+        }
+    }
+
+    fn receive(&mut self, buf: &mut [u8]) {
+        let mut state = self.state.borrow_mut();
+        for b in buf {
+            *b = state.reads.pop_front().unwrap_or(0);
+        }
+    }
+}
+
+impl ErrorType for MockSpi {
+    type Error = SpiError;
+}
+
+impl SpiDevice for MockSpi {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        self.begin()?;
+
+        for op in operations {
+            match op {
+                Operation::Read(buf) => self.receive(buf),
+                Operation::Write(bytes) => self.record(bytes),
+                Operation::Transfer(read, write) => {
+                    self.record(write);
+                    self.receive(read);
+                }
+                Operation::TransferInPlace(buf) => {
+                    self.record(buf);
+                    self.receive(buf);
+                }
+                Operation::DelayNs(ns) => self.state.borrow_mut().delay_ns += *ns as u64,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SpiBus for MockSpi {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.receive(words);
+        Ok(())
+    }
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.record(words);
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        self.record(write);
+        self.receive(read);
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.record(words);
+        self.receive(words);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
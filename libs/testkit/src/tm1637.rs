@@ -0,0 +1,212 @@
+//! Emulator TM1637: decode start/stop/bit dari level CLK dan DIO,
+//! chip menarik DIO low pada clock ke-9 sebagai ACK.
+
+use core::cell::RefCell;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+use crate::digital::PinError;
+
+const DIGITS: usize = 6;
+
+struct Chip {
+    present: bool,
+    clk: bool,
+    dio: bool,
+
+    // bit dalam frame: 0..8 data, 8 = clock ACK
+    bit: u8,
+    byte: u8,
+    ack: bool,
+    // byte pertama setelah start adalah perintah
+    first: bool,
+    addr: usize,
+    fixed: bool,
+
+    segments: [u8; DIGITS],
+    brightness: u8,
+    on: bool,
+    commands: Vec<u8>,
+}
+
+impl Chip {
+    fn new() -> Self {
+        Self {
+            present: true,
+            clk: true,
+            dio: true,
+            bit: 0,
+            byte: 0,
+            ack: false,
+            first: false,
+            addr: 0,
+            fixed: false,
+            segments: [0; DIGITS],
+            brightness: 0,
+            on: false,
+            commands: Vec::new(),
+        }
+    }
+
+    fn set_clk(&mut self, level: bool) {
+        let (rising, falling) = (!self.clk && level, self.clk && !level);
+        self.clk = level;
+
+        if rising && self.bit < 8 {
+            self.byte |= (self.dio as u8) << self.bit;
+            self.bit += 1;
+        } else if falling && self.bit == 8 {
+            if self.ack {
+                // clock ACK selesai
+                self.ack = false;
+                self.bit = 0;
+                self.byte = 0;
+            } else {
+                self.ack = self.present;
+                self.receive(self.byte);
+                if !self.present {
+                    self.bit = 0;
+                    self.byte = 0;
+                }
+            }
+        }
+    }
+
+    fn set_dio(&mut self, level: bool) {
+        if self.clk && self.dio && !level {
+            // start
+            self.first = true;
+            self.bit = 0;
+            self.byte = 0;
+        }
+        self.dio = level;
+    }
+
+    fn read_dio(&self) -> bool {
+        self.dio && !self.ack
+    }
+
+    fn receive(&mut self, byte: u8) {
+        if !self.present {
+            return;
+        }
+        if !self.first {
+            if self.addr < DIGITS {
+                self.segments[self.addr] = byte;
+            }
+            if !self.fixed {
+                self.addr += 1;
+            }
+            return;
+        }
+
+        self.first = false;
+        self.commands.push(byte);
+        match byte & 0xC0 {
+            0x40 => self.fixed = byte & 0x04 != 0,
+            0xC0 => self.addr = (byte & 0x07) as usize,
+            0x80 => {
+                self.on = byte & 0x08 != 0;
+                self.brightness = byte & 0x07;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Modul TM1637 virtual; `clk()`/`dio()` memberi dua pin yang berbagi chip
+#[derive(Clone)]
+pub struct Tm1637Chip {
+    chip: Rc<RefCell<Chip>>,
+}
+
+impl Default for Tm1637Chip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tm1637Chip {
+    pub fn new() -> Self {
+        Self {
+            chip: Rc::new(RefCell::new(Chip::new())),
+        }
+    }
+
+    pub fn clk(&self) -> Tm1637Pin {
+        Tm1637Pin { chip: self.clone(), dio: false }
+    }
+
+    pub fn dio(&self) -> Tm1637Pin {
+        Tm1637Pin { chip: self.clone(), dio: true }
+    }
+
+    /// Cabut/pasang modul; saat dicabut tidak ada ACK
+    pub fn set_present(&self, present: bool) {
+        self.chip.borrow_mut().present = present;
+    }
+
+    /// Pola segmen per digit (bit 0..6 = a..g, bit 7 = titik)
+    pub fn segments(&self) -> [u8; DIGITS] {
+        self.chip.borrow().segments
+    }
+
+    pub fn brightness(&self) -> u8 {
+        self.chip.borrow().brightness
+    }
+
+    pub fn display_on(&self) -> bool {
+        self.chip.borrow().on
+    }
+
+    /// Byte perintah (byte pertama setiap frame) yang diterima
+    pub fn commands(&self) -> Vec<u8> {
+        self.chip.borrow().commands.clone()
+    }
+}
+
+/// Pin CLK atau DIO yang tersambung ke `Tm1637Chip`
+pub struct Tm1637Pin {
+    chip: Tm1637Chip,
+    dio: bool,
+}
+
+impl ErrorType for Tm1637Pin {
+    type Error = PinError;
+}
+
+impl OutputPin for Tm1637Pin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set(true);
+        Ok(())
+    }
+}
+
+impl InputPin for Tm1637Pin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        let chip = self.chip.chip.borrow();
+        Ok(if self.dio { chip.read_dio() } else { chip.clk })
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+impl Tm1637Pin {
+    fn set(&mut self, level: bool) {
+        let mut chip = self.chip.chip.borrow_mut();
+        if self.dio {
+            chip.set_dio(level);
+        } else {
+            chip.set_clk(level);
+        }
+    }
+}
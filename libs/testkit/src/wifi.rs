@@ -0,0 +1,47 @@
+use std::collections::VecDeque;
+use std::string::String;
+
+use drivers::wifi::WifiStatus;
+use services::wifi::Wifi;
+
+/// Mock `services::wifi::Wifi` dengan hasil connect yang bisa diskrip
+#[derive(Default)]
+pub struct MockWifi {
+    pub connected: bool,
+    pub last_ssid: Option<String>,
+    pub connects: u32,
+    outcomes: VecDeque<bool>,
+}
+
+impl MockWifi {
+    /// Hasil `connect` berikutnya (FIFO); bila kosong, connect selalu berhasil
+    pub fn script(outcomes: &[bool]) -> Self {
+        Self {
+            outcomes: outcomes.iter().copied().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Link putus dari sisi access point
+    pub fn drop_link(&mut self) {
+        self.connected = false;
+    }
+}
+
+impl Wifi for MockWifi {
+    fn connect(&mut self, ssid: &str, _password: &str) -> WifiStatus {
+        self.connects += 1;
+        self.last_ssid = Some(ssid.into());
+        self.connected = self.outcomes.pop_front().unwrap_or(true);
+
+        if self.connected {
+            WifiStatus::Connected
+        } else {
+            WifiStatus::Disconnected
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+}